@@ -1,7 +1,7 @@
 //! Calculations and processing of numeric values.
 
 use std::cmp::Ordering;
-use std::ops::Rem;
+use std::ops::{Add, Mul, Rem, Sub};
 
 use typst::eval::{Module, Scope};
 
@@ -22,16 +22,20 @@ pub fn module() -> Module {
     scope.define("sinh", sinh);
     scope.define("cosh", cosh);
     scope.define("tanh", tanh);
+    scope.define("exp", exp);
+    scope.define("ln", ln);
     scope.define("log", log);
     scope.define("floor", floor);
     scope.define("ceil", ceil);
     scope.define("round", round);
     scope.define("clamp", clamp);
+    scope.define("lerp", lerp);
     scope.define("min", min);
     scope.define("max", max);
     scope.define("even", even);
     scope.define("odd", odd);
     scope.define("mod", mod_);
+    scope.define("quo", quo);
     scope.define("inf", Value::Float(f64::INFINITY));
     scope.define("nan", Value::Float(f64::NAN));
     scope.define("pi", Value::Float(std::f64::consts::PI));
@@ -75,9 +79,15 @@ cast_from_value! {
 
 /// Raise a value to some exponent.
 ///
+/// The result is an integer if the base is an integer and the exponent is a
+/// non-negative integer, unless it is too large for an integer. In all other
+/// cases, the result is a float.
+///
 /// ## Example
 /// ```example
-/// #calc.pow(2, 3)
+/// #calc.pow(2, 3) \
+/// #calc.pow(2, -1) \
+/// #calc.pow(4, 0.5)
 /// ```
 ///
 /// Display: Power
@@ -87,20 +97,20 @@ cast_from_value! {
 pub fn pow(
     /// The base of the power.
     base: Num,
-    /// The exponent of the power. Must be non-negative.
+    /// The exponent of the power.
     exponent: Spanned<Num>,
 ) -> Value {
-    let exponent = match exponent.v {
-        Num::Int(i) if i > u32::MAX as i64 => {
-            bail!(exponent.span, "exponent too large");
-        }
-        Num::Int(0..) => exponent.v,
-        Num::Float(f) if f >= 0.0 => exponent.v,
-        _ => {
-            bail!(exponent.span, "exponent must be non-negative");
+    if let (Num::Int(a), Num::Int(b)) = (base, exponent.v) {
+        if let Some(v) = u32::try_from(b).ok().and_then(|b| a.checked_pow(b)) {
+            return Ok(Value::Int(v));
         }
-    };
-    base.apply2(exponent, |a, b| a.pow(b as u32), f64::powf)
+    }
+
+    let result = base.float().powf(exponent.v.float());
+    if result.is_nan() && !base.float().is_nan() && !exponent.v.float().is_nan() {
+        bail!(exponent.span, "the result is not a real number");
+    }
+    Value::Float(result)
 }
 
 /// Calculate the square root of a number.
@@ -241,7 +251,7 @@ pub fn asin(
 /// Returns: angle
 #[func]
 pub fn acos(
-    /// The number whose arcsine to calculate. Must be between -1 and 1.
+    /// The number whose arccosine to calculate. Must be between -1 and 1.
     value: Spanned<Num>,
 ) -> Value {
     let val = value.v.float();
@@ -345,13 +355,56 @@ pub fn tanh(
     })
 }
 
+/// Calculate the exponential function of a number, that is, raise Euler's
+/// number to the power of it.
+///
+/// ## Example
+/// ```example
+/// #calc.exp(1) \
+/// #calc.exp(0.5)
+/// ```
+///
+/// Display: Exponential
+/// Category: calculate
+/// Returns: float
+#[func]
+pub fn exp(
+    /// The exponent of the power.
+    exponent: f64,
+) -> Value {
+    Value::Float(exponent.exp())
+}
+
+/// Calculate the natural logarithm of a number.
+///
+/// ## Example
+/// ```example
+/// #calc.ln(calc.e) \
+/// #calc.ln(10)
+/// ```
+///
+/// Display: Natural Logarithm
+/// Category: calculate
+/// Returns: float
+#[func]
+pub fn ln(
+    /// The number whose logarithm to calculate. Must be positive.
+    value: Spanned<f64>,
+) -> Value {
+    if value.v <= 0.0 {
+        bail!(value.span, "value must be strictly positive");
+    }
+    Value::Float(value.v.ln())
+}
+
 /// Calculate the logarithm of a number.
 ///
 /// If the base is not specified, the logarithm is calculated in base 10.
 ///
 /// ## Example
 /// ```example
-/// #calc.log(100)
+/// #calc.log(100) \
+/// #calc.log(8, base: 2)
 /// ```
 ///
 /// Display: Logarithm
@@ -359,13 +412,20 @@ pub fn tanh(
 /// Returns: float
 #[func]
 pub fn log(
-    /// The number whose logarithm to calculate.
-    value: f64,
-    /// The base of the logarithm.
+    /// The number whose logarithm to calculate. Must be positive.
+    value: Spanned<f64>,
+    /// The base of the logarithm. Must be positive and not one.
     #[named]
-    #[default(10.0)]
-    base: f64,
+    #[default(Spanned::new(10.0, Span::detached()))]
+    base: Spanned<f64>,
 ) -> Value {
+    if value.v <= 0.0 {
+        bail!(value.span, "value must be strictly positive");
+    }
+    if base.v <= 0.0 || base.v == 1.0 {
+        bail!(base.span, "base must be strictly positive and not one");
+    }
+    let (value, base) = (value.v, base.v);
     Value::Float(if base == 2.0 {
         value.log2()
     } else if base == 10.0 {
@@ -377,7 +437,8 @@ pub fn log(
 
 /// Round a number down to the nearest integer.
 ///
-/// If the number is already an integer, it is returned unchanged.
+/// If the number is already an integer, it is returned unchanged. Lengths and
+/// angles are rounded down to whole points and degrees, respectively.
 ///
 /// ## Example
 /// ```example
@@ -388,21 +449,19 @@ pub fn log(
 ///
 /// Display: Round down
 /// Category: calculate
-/// Returns: integer
+/// Returns: integer, length or angle
 #[func]
 pub fn floor(
     /// The number to round down.
-    value: Num,
+    value: Roundable,
 ) -> Value {
-    match value {
-        Num::Int(n) => Value::Int(n),
-        Num::Float(n) => Value::Int(n.floor() as i64),
-    }
+    value.apply(f64::floor)
 }
 
 /// Round a number up to the nearest integer.
 ///
-/// If the number is already an integer, it is returned unchanged.
+/// If the number is already an integer, it is returned unchanged. Lengths and
+/// angles are rounded up to whole points and degrees, respectively.
 ///
 /// ## Example
 /// ```example
@@ -413,21 +472,19 @@ pub fn floor(
 ///
 /// Display: Round up
 /// Category: calculate
-/// Returns: integer
+/// Returns: integer, length or angle
 #[func]
 pub fn ceil(
     /// The number to round up.
-    value: Num,
+    value: Roundable,
 ) -> Value {
-    match value {
-        Num::Int(n) => Value::Int(n),
-        Num::Float(n) => Value::Int(n.ceil() as i64),
-    }
+    value.apply(f64::ceil)
 }
 
 /// Round a number to the nearest integer.
 ///
-/// Optionally, a number of decimal places can be specified.
+/// Optionally, a number of decimal places can be specified. Lengths and angles
+/// are rounded in points and degrees, respectively.
 ///
 /// ## Example
 /// ```example
@@ -438,51 +495,198 @@ pub fn ceil(
 ///
 /// Display: Round
 /// Category: calculate
-/// Returns: integer or float
+/// Returns: integer, float, length or angle
 #[func]
 pub fn round(
     /// The number to round.
-    value: Num,
+    value: Roundable,
     /// The number of decimal places.
     #[named]
     #[default(0)]
     digits: i64,
 ) -> Value {
+    let factor = 10.0_f64.powi(digits as i32);
     match value {
-        Num::Int(n) if digits == 0 => Value::Int(n),
-        _ => {
-            let n = value.float();
-            let factor = 10.0_f64.powi(digits as i32) as f64;
-            Value::Float((n * factor).round() / factor)
-        }
+        Roundable::Int(n) if digits == 0 => Value::Int(n),
+        Roundable::Int(n) => Value::Float((n as f64 * factor).round() / factor),
+        Roundable::Float(n) => Value::Float((n * factor).round() / factor),
+        _ => value.apply(|n| (n * factor).round() / factor),
     }
 }
 
-/// Clamp a number between a minimum and maximum value.
+/// Clamp a value between a minimum and maximum value.
+///
+/// Works with numbers, lengths, angles, ratios, and fractions. All three
+/// values must be of the same kind, except that integers and floats can be
+/// mixed.
 ///
 /// ## Example
 /// ```example
 /// #assert(calc.clamp(5, 0, 10) == 5)
 /// #assert(calc.clamp(5, 6, 10) == 6)
-/// #calc.clamp(5, 0, 4)
+/// #calc.clamp(5, 0, 4) \
+/// #calc.clamp(-2pt, 0pt, 1cm)
 /// ```
 ///
 /// Display: Clamp
 /// Category: calculate
-/// Returns: integer or float
+/// Returns: any
 #[func]
 pub fn clamp(
-    /// The number to clamp.
-    value: Num,
+    /// The value to clamp.
+    value: ToClamp,
     /// The inclusive minimum value.
-    min: Num,
+    min: ToClamp,
     /// The inclusive maximum value.
-    max: Spanned<Num>,
+    max: Spanned<ToClamp>,
 ) -> Value {
-    if max.v.float() < min.float() {
-        bail!(max.span, "max must be greater than or equal to min")
+    let (max, max_span) = (max.v, max.span);
+    if let (ToClamp::Num(value), ToClamp::Num(min), ToClamp::Num(max)) =
+        (&value, &min, &max)
+    {
+        if max.float() < min.float() {
+            bail!(max_span, "max must be greater than or equal to min")
+        }
+        return Ok(value.apply3(*min, *max, i64::clamp, f64::clamp));
+    }
+
+    let (value, min, max) = (value.into_value(), min.into_value(), max.into_value());
+    let compare = |a: &Value, b: &Value| {
+        a.partial_cmp(b).ok_or_else(|| {
+            eco_format!("cannot compare {} and {}", a.type_name(), b.type_name())
+        })
+    };
+
+    if compare(&max, &min).at(args.span)? == Ordering::Less {
+        bail!(max_span, "max must be greater than or equal to min")
     }
-    value.apply3(min, max.v, i64::clamp, f64::clamp)
+
+    if compare(&value, &min).at(args.span)? == Ordering::Less {
+        min
+    } else if compare(&value, &max).at(args.span)? == Ordering::Greater {
+        max
+    } else {
+        value
+    }
+}
+
+/// A value that can be clamped.
+enum ToClamp {
+    Num(Num),
+    Other(Value),
+}
+
+impl ToClamp {
+    fn into_value(self) -> Value {
+        match self {
+            Self::Num(Num::Int(v)) => Value::Int(v),
+            Self::Num(Num::Float(v)) => Value::Float(v),
+            Self::Other(v) => v,
+        }
+    }
+}
+
+cast_from_value! {
+    ToClamp,
+    v: Num => Self::Num(v),
+    v: Length => Self::Other(Value::Length(v)),
+    v: Angle => Self::Other(Value::Angle(v)),
+    v: Ratio => Self::Other(Value::Ratio(v)),
+    v: Fr => Self::Other(Value::Fraction(v)),
+}
+
+/// Linearly interpolate between two values.
+///
+/// Works with numbers, lengths, angles, ratios, relative lengths, and
+/// fractions. Both values must be of the same kind, except that integers and
+/// floats can be mixed. Numbers are always interpolated as floats.
+///
+/// A factor outside of `{0%}` to `{100%}` extrapolates beyond the two values
+/// instead of being clamped.
+///
+/// ## Example
+/// ```example
+/// #calc.lerp(0pt, 10pt, 25%) \
+/// #calc.lerp(0deg, 90deg, 0.5) \
+/// #calc.lerp(1, 2, 150%)
+/// ```
+///
+/// Display: Linear Interpolation
+/// Category: calculate
+/// Returns: any
+#[func]
+pub fn lerp(
+    /// The value at `{0%}`.
+    from: ToLerp,
+    /// The value at `{100%}`.
+    to: ToLerp,
+    /// How far to go from the first to the second value.
+    t: LerpFactor,
+) -> Value {
+    fn interpolate<T>(a: T, b: T, t: f64) -> T
+    where
+        T: Copy + Add<Output = T> + Sub<Output = T> + Mul<f64, Output = T>,
+    {
+        a + (b - a) * t
+    }
+
+    let t = t.0;
+    match (from, to) {
+        (ToLerp::Float(a), ToLerp::Float(b)) => Value::Float(interpolate(a, b, t)),
+        (ToLerp::Length(a), ToLerp::Length(b)) => Value::Length(interpolate(a, b, t)),
+        (ToLerp::Angle(a), ToLerp::Angle(b)) => Value::Angle(interpolate(a, b, t)),
+        (ToLerp::Ratio(a), ToLerp::Ratio(b)) => Value::Ratio(interpolate(a, b, t)),
+        (ToLerp::Rel(a), ToLerp::Rel(b)) => Value::Relative(interpolate(a, b, t)),
+        (ToLerp::Fr(a), ToLerp::Fr(b)) => Value::Fraction(interpolate(a, b, t)),
+        (a, b) => bail!(
+            args.span,
+            "cannot interpolate between {} and {}",
+            a.type_name(),
+            b.type_name()
+        ),
+    }
+}
+
+/// A value that can be interpolated.
+enum ToLerp {
+    Float(f64),
+    Length(Length),
+    Angle(Angle),
+    Ratio(Ratio),
+    Rel(Rel<Length>),
+    Fr(Fr),
+}
+
+impl ToLerp {
+    fn type_name(&self) -> &'static str {
+        match self {
+            Self::Float(_) => "number",
+            Self::Length(_) => "length",
+            Self::Angle(_) => "angle",
+            Self::Ratio(_) => "ratio",
+            Self::Rel(_) => "relative length",
+            Self::Fr(_) => "fraction",
+        }
+    }
+}
+
+cast_from_value! {
+    ToLerp,
+    v: Num => Self::Float(v.float()),
+    v: Length => Self::Length(v),
+    v: Angle => Self::Angle(v),
+    v: Ratio => Self::Ratio(v),
+    v: Rel<Length> => Self::Rel(v),
+    v: Fr => Self::Fr(v),
+}
+
+/// A factor for interpolation.
+struct LerpFactor(f64);
+
+cast_from_value! {
+    LerpFactor,
+    v: f64 => Self(v),
+    v: Ratio => Self(v.get()),
 }
 
 /// Determine the minimum of a sequence of values.
@@ -599,10 +803,15 @@ pub fn odd(
 
 /// Calculate the modulus of two numbers.
 ///
+/// The result has the same sign as the dividend, so that
+/// `{calc.quo(a, b) * b + calc.mod(a, b) == a}` holds when both numbers are
+/// positive.
+///
 /// ## Example
 /// ```example
 /// #calc.mod(20, 6) \
-/// #calc.mod(1.75, 0.5)
+/// #calc.mod(1.75, 0.5) \
+/// #calc.mod(-7, 3)
 /// ```
 ///
 /// Display: Modulus
@@ -618,7 +827,48 @@ pub fn mod_(
     if divisor.v.float() == 0.0 {
         bail!(divisor.span, "divisor must not be zero");
     }
-    dividend.apply2(divisor.v, Rem::rem, Rem::rem)
+    dividend.apply2(divisor.v, i64::wrapping_rem, Rem::rem)
+}
+
+/// Divide two numbers and round the result down.
+///
+/// The result is an integer if both numbers are integers and a float
+/// otherwise.
+///
+/// Since the result is rounded towards negative infinity while
+/// [`calc.mod`]($category/calculate/mod) truncates, the two don't pair up
+/// for operands of different signs: `{calc.quo(-7, 2)}` is `{-4}`, but
+/// `{calc.mod(-7, 2)}` is `{-1}`. The remainder matching this function is
+/// `{a - calc.quo(a, b) * b}`.
+///
+/// ## Example
+/// ```example
+/// #calc.quo(20, 6) \
+/// #calc.quo(-7, 2) \
+/// #calc.quo(1.75, 0.5)
+/// ```
+///
+/// Display: Quotient
+/// Category: calculate
+/// Returns: integer or float
+#[func]
+pub fn quo(
+    /// The dividend of the quotient.
+    dividend: Num,
+    /// The divisor of the quotient.
+    divisor: Spanned<Num>,
+) -> Value {
+    if divisor.v.float() == 0.0 {
+        bail!(divisor.span, "divisor must not be zero");
+    }
+    match (dividend, divisor.v) {
+        (Num::Int(a), Num::Int(b)) => match a.checked_div(b) {
+            Some(q) if a % b != 0 && (a < 0) != (b < 0) => Value::Int(q - 1),
+            Some(q) => Value::Int(q),
+            None => Value::Float((a as f64 / b as f64).floor()),
+        },
+        (a, b) => Value::Float((a.float() / b.float()).floor()),
+    }
 }
 
 /// A value which can be passed to functions that work with integers and floats.
@@ -668,6 +918,39 @@ cast_from_value! {
     v: f64 => Self::Float(v),
 }
 
+/// A value that can be passed to a rounding function.
+#[derive(Debug, Copy, Clone)]
+enum Roundable {
+    Int(i64),
+    Float(f64),
+    Length(Length),
+    Angle(Angle),
+}
+
+impl Roundable {
+    /// Apply a rounding function to the numeric part of the value, turning
+    /// floats into integers and preserving units.
+    fn apply(self, f: impl Fn(f64) -> f64) -> Value {
+        match self {
+            Self::Int(n) => Value::Int(n),
+            Self::Float(n) => Value::Int(f(n) as i64),
+            Self::Length(v) => Value::Length(Length {
+                abs: Abs::pt(f(v.abs.to_pt())),
+                em: Em::new(f(v.em.get())),
+            }),
+            Self::Angle(v) => Value::Angle(Angle::deg(f(v.to_deg()))),
+        }
+    }
+}
+
+cast_from_value! {
+    Roundable,
+    v: i64 => Self::Int(v),
+    v: f64 => Self::Float(v),
+    v: Length => Self::Length(v),
+    v: Angle => Self::Angle(v),
+}
+
 /// A value that can be passed to a trigonometric function.
 enum AngleLike {
     Int(i64),
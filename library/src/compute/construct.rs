@@ -1,4 +1,4 @@
-use std::num::NonZeroI64;
+use std::num::{NonZeroI64, NonZeroUsize};
 use std::str::FromStr;
 
 use typst::eval::Regex;
@@ -9,14 +9,16 @@ use crate::prelude::*;
 ///
 /// - Booleans are converted to `0` or `1`.
 /// - Floats are floored to the next 64-bit integer.
-/// - Strings are parsed in base 10.
+/// - Strings are parsed in base 10, or in the given `base`. They may start
+///   with a minus sign, but not with a prefix like `0x`.
 ///
 /// ## Example
 /// ```example
 /// #int(false) \
 /// #int(true) \
 /// #int(2.7) \
-/// #{ int("27") + int("4") }
+/// #{ int("27") + int("4") } \
+/// #int("ff", base: 16)
 /// ```
 ///
 /// Display: Integer
@@ -25,20 +27,35 @@ use crate::prelude::*;
 #[func]
 pub fn int(
     /// The value that should be converted to an integer.
-    value: ToInt,
+    value: Spanned<ToInt>,
+    /// The base to parse a string in, between 2 and 36. Digits above nine
+    /// are the letters `a` to `z`, in either case.
+    #[named]
+    #[default(Spanned::new(10, Span::detached()))]
+    base: Spanned<i64>,
 ) -> Value {
-    Value::Int(value.0)
+    let radix = radix(base)?;
+    Value::Int(match value.v {
+        ToInt::Int(v) if radix == 10 => v,
+        ToInt::Int(_) => bail!(base.span, "base is only supported for strings"),
+        ToInt::Str(v) => i64::from_str_radix(&v, radix)
+            .map_err(|_| "not a valid integer")
+            .at(value.span)?,
+    })
 }
 
 /// A value that can be cast to an integer.
-struct ToInt(i64);
+enum ToInt {
+    Int(i64),
+    Str(EcoString),
+}
 
 cast_from_value! {
     ToInt,
-    v: bool => Self(v as i64),
-    v: i64 => Self(v),
-    v: f64 => Self(v as i64),
-    v: EcoString => Self(v.parse().map_err(|_| "not a valid integer")?),
+    v: bool => Self::Int(v as i64),
+    v: i64 => Self::Int(v),
+    v: f64 => Self::Int(v as i64),
+    v: EcoString => Self::Str(v),
 }
 
 /// Convert a value to a float.
@@ -122,7 +139,8 @@ pub fn rgb(
     /// The color in hexadecimal notation.
     ///
     /// Accepts three, four, six or eight hexadecimal digits and optionally
-    /// a leading hashtag.
+    /// a leading hashtag. The name of a built-in color, like `{"red"}`, is
+    /// accepted as well, which is useful for colors loaded from data files.
     ///
     /// If this string is given, the individual components should not be given.
     ///
@@ -154,7 +172,10 @@ pub fn rgb(
     Value::Color(if let Some(string) = args.find::<Spanned<EcoString>>()? {
         match RgbaColor::from_str(&string.v) {
             Ok(color) => color.into(),
-            Err(msg) => bail!(string.span, msg),
+            Err(msg) => match Color::from_name(&string.v) {
+                Some(color) => color,
+                None => bail!(string.span, msg),
+            },
         }
     } else {
         let Component(r) = args.expect("red component")?;
@@ -165,6 +186,42 @@ pub fn rgb(
     })
 }
 
+/// Mix two colors.
+///
+/// Interpolates linearly between the colors' red, green, blue and alpha
+/// channels. The result is always an RGBA color.
+///
+/// ## Example
+/// ```example
+/// #square(fill: mix(red, blue, 50%))
+/// #square(fill: mix(red, blue, 0.8))
+/// ```
+///
+/// Display: Mix
+/// Category: construct
+/// Returns: color
+#[func]
+pub fn mix(
+    /// The first color.
+    a: Color,
+    /// The second color.
+    b: Color,
+    /// How much of the second color to mix in. Values below `{0%}` or above
+    /// `{100%}` are clamped.
+    t: MixFactor,
+) -> Value {
+    Value::Color(a.mix(b, t.0))
+}
+
+/// A float or ratio mix factor.
+struct MixFactor(f64);
+
+cast_from_value! {
+    MixFactor,
+    v: f64 => Self(v),
+    v: Ratio => Self(v.get()),
+}
+
 /// An integer or ratio component.
 struct Component(u8);
 
@@ -181,6 +238,36 @@ cast_from_value! {
     },
 }
 
+/// Create an RGB color from hue, saturation and lightness.
+///
+/// This is often more intuitive than specifying the red, green and blue
+/// components directly. The resulting color is opaque and specified in the
+/// sRGB color space.
+///
+/// ## Example
+/// ```example
+/// #for x in range(0, 360, step: 60) {
+///   box(square(size: 9pt, fill: hsl(x * 1deg, 80%, 50%)))
+/// }
+/// ```
+///
+/// Display: HSL
+/// Category: construct
+/// Returns: color
+#[func]
+pub fn hsl(
+    /// The hue as an angle on the color wheel, where `{0deg}` is red.
+    hue: Angle,
+    /// The saturation, from gray at `{0%}` to the most vivid color at `{100%}`.
+    /// Values outside of this range are clamped.
+    saturation: Ratio,
+    /// The lightness, from black at `{0%}` to white at `{100%}`. Values outside
+    /// of this range are clamped.
+    lightness: Ratio,
+) -> Value {
+    Value::Color(RgbaColor::from_hsl(hue, saturation, lightness).into())
+}
+
 /// Create a CMYK color.
 ///
 /// This is useful if you want to target a specific printer. The conversion
@@ -284,16 +371,28 @@ cast_from_value! {
 
 /// Convert a value to a string.
 ///
-/// - Integers are formatted in base 10.
+/// - Integers are formatted in base 10, or in the given `base`. Negative
+///   numbers start with a minus sign in every base, and no prefix like `0x`
+///   is added.
 /// - Floats are formatted in base 10 and never in exponential notation.
+/// - Booleans are converted to `{"true"}` or `{"false"}`.
+/// - Lengths, angles, ratios, fractions, and colors are formatted the same
+///   way as by [`repr`]($func/repr).
 /// - From labels the name is extracted.
 ///
+/// Arrays, dictionaries, content, and functions can't be converted, as there
+/// is no single meaningful string for them. Use [`repr`]($func/repr) to get
+/// their source code representation instead.
+///
 /// ## Example
 /// ```example
 /// #str(10) \
 /// #str(2.7) \
 /// #str(1e8) \
-/// #str(<intro>)
+/// #str(<intro>) \
+/// #str(true) \
+/// #str(2.5cm) \
+/// #str(255, base: 16)
 /// ```
 ///
 /// Display: String
@@ -303,19 +402,101 @@ cast_from_value! {
 pub fn str(
     /// The value that should be converted to a string.
     value: ToStr,
+    /// The base to format an integer in, between 2 and 36. Digits above nine
+    /// are the lowercase letters `a` to `z`.
+    #[named]
+    #[default(Spanned::new(10, Span::detached()))]
+    base: Spanned<i64>,
 ) -> Value {
-    Value::Str(value.0)
+    let radix = radix(base)?;
+    Value::Str(match value {
+        ToStr::Int(v) => format_int(v, radix),
+        ToStr::Str(v) if radix == 10 => v,
+        ToStr::Str(_) => bail!(base.span, "base is only supported for integers"),
+    })
 }
 
 /// A value that can be cast to a string.
-struct ToStr(Str);
+enum ToStr {
+    Int(i64),
+    Str(Str),
+}
 
 cast_from_value! {
     ToStr,
-    v: i64 => Self(format_str!("{}", v)),
-    v: f64 => Self(format_str!("{}", v)),
-    v: Label => Self(v.0.into()),
-    v: Str => Self(v),
+    v: i64 => Self::Int(v),
+    v: f64 => Self::Str(format_str!("{}", v)),
+    v: bool => Self::Str(format_str!("{}", v)),
+    v: Length => Self::Str(format_str!("{:?}", v)),
+    v: Angle => Self::Str(format_str!("{:?}", v)),
+    v: Ratio => Self::Str(format_str!("{:?}", v)),
+    v: Rel<Length> => Self::Str(format_str!("{:?}", v)),
+    v: Fr => Self::Str(format_str!("{:?}", v)),
+    v: Color => Self::Str(format_str!("{:?}", v)),
+    v: Label => Self::Str(v.0.into()),
+    v: Str => Self::Str(v),
+}
+
+/// Check that a base for integer conversion is supported.
+fn radix(base: Spanned<i64>) -> SourceResult<u32> {
+    match base.v {
+        2..=36 => Ok(base.v as u32),
+        _ => bail!(base.span, "base must be between 2 and 36"),
+    }
+}
+
+/// Format an integer in the given base.
+fn format_int(v: i64, radix: u32) -> Str {
+    if radix == 10 {
+        return format_str!("{}", v);
+    }
+
+    let mut n = v.unsigned_abs();
+    let mut digits = vec![];
+    loop {
+        digits.push(std::char::from_digit((n % radix as u64) as u32, radix).unwrap());
+        n /= radix as u64;
+        if n == 0 {
+            break;
+        }
+    }
+
+    if v < 0 {
+        digits.push('-');
+    }
+
+    digits.into_iter().rev().collect::<String>().into()
+}
+
+/// Convert a value to an array.
+///
+/// - `{none}` is converted to an empty array.
+/// - Arrays are returned unchanged.
+/// - Any other value is wrapped in an array with a single item.
+///
+/// This is useful for normalizing arguments that accept zero, one, or many
+/// values.
+///
+/// ## Example
+/// ```example
+/// #array(none) \
+/// #array(1) \
+/// #array((1, 2))
+/// ```
+///
+/// Display: Array
+/// Category: construct
+/// Returns: array
+#[func]
+pub fn array(
+    /// The value that should be converted to an array.
+    value: Value,
+) -> Value {
+    Value::Array(match value {
+        Value::None => Array::new(),
+        Value::Array(array) => array,
+        v => array![v],
+    })
 }
 
 /// Create a label from a string.
@@ -435,3 +616,88 @@ pub fn range(
 
     Value::Array(array)
 }
+
+/// Create an array of evenly spaced floats between two numbers.
+///
+/// Both `start` and `end` are included in the resulting array.
+///
+/// ## Example
+/// ```example
+/// #linspace(0, 1, 5) \
+/// #linspace(10, 0, 3) \
+/// #linspace(2, 4, 1)
+/// ```
+///
+/// Display: Linspace
+/// Category: construct
+/// Returns: array
+#[func]
+pub fn linspace(
+    /// The first number in the array.
+    start: f64,
+    /// The last number in the array.
+    end: f64,
+    /// How many numbers to generate. If this is one, the array only contains
+    /// `start`.
+    count: Spanned<NonZeroUsize>,
+) -> Value {
+    if count.v.get() > MAX_NUMBERS {
+        bail!(count.span, "cannot generate more than {} numbers", MAX_NUMBERS);
+    }
+
+    let count = count.v.get();
+
+    let step = (end - start) / (count - 1).max(1) as f64;
+
+    // Hit the end exactly despite rounding errors.
+    let x = |i| if i > 0 && i == count - 1 { end } else { start + i as f64 * step };
+    Value::Array((0..count).map(|i| Value::Float(x(i))).collect())
+}
+
+/// Create an array of floats from a start value up to an end value.
+///
+/// Like [`range`]($func/range), but works with floats. The `end` is not
+/// included in the resulting array.
+///
+/// ## Example
+/// ```example
+/// #arange(0, 1, 0.25) \
+/// #arange(1, 0, -0.5)
+/// ```
+///
+/// Display: Arange
+/// Category: construct
+/// Returns: array
+#[func]
+pub fn arange(
+    /// The start of the range (inclusive).
+    start: Spanned<f64>,
+    /// The end of the range (exclusive).
+    end: Spanned<f64>,
+    /// The distance between the generated numbers. May be negative, but not
+    /// zero.
+    step: Spanned<f64>,
+) -> Value {
+    for number in [&start, &end, &step] {
+        if !number.v.is_finite() {
+            bail!(number.span, "number must be finite");
+        }
+    }
+
+    if step.v == 0.0 {
+        bail!(step.span, "step must not be zero");
+    }
+
+    let count = ((end.v - start.v) / step.v).ceil().max(0.0);
+    if count > MAX_NUMBERS as f64 {
+        bail!(args.span, "cannot generate more than {} numbers", MAX_NUMBERS);
+    }
+
+    let array = (0..count as usize)
+        .map(|i| Value::Float(start.v + i as f64 * step.v))
+        .collect();
+    Value::Array(array)
+}
+
+/// The maximum number of numbers generated by `linspace` and `arange`.
+const MAX_NUMBERS: usize = 1_000_000;
@@ -1,6 +1,7 @@
 use std::fmt::Write;
 
 use typst::diag::{format_xml_like_error, FileError};
+use typst::eval::{FuncInfo, Lazy, NativeFunc};
 
 use crate::prelude::*;
 
@@ -138,6 +139,10 @@ fn format_csv_error(error: csv::Error) -> String {
 ///
 /// The function returns a dictionary or an array, depending on the JSON file.
 ///
+/// Instead of a path, you can also pass JSON source code directly through the
+/// `data` argument. This is useful for data that was assembled from strings or
+/// loaded with [`read`]($func/read).
+///
 /// The JSON files in the example contain objects with the keys `temperature`,
 /// `unit`, and `weather`.
 ///
@@ -172,13 +177,33 @@ fn format_csv_error(error: csv::Error) -> String {
 #[func]
 pub fn json(
     /// Path to a JSON file.
-    path: Spanned<EcoString>,
+    ///
+    /// If this is given, `data` should not be given.
+    #[external]
+    path: EcoString,
+    /// JSON source code to parse instead of reading a file.
+    ///
+    /// ```example
+    /// #let point = json(data: "{\"x\": 1, \"y\": 2.5}")
+    /// #point.x, #point.y
+    /// ```
+    #[external]
+    #[named]
+    data: EcoString,
 ) -> Value {
-    let Spanned { v: path, span } = path;
-    let path = vm.locate(&path).at(span)?;
-    let data = vm.world().file(&path).at(span)?;
-    let value: serde_json::Value =
-        serde_json::from_slice(&data).map_err(format_json_error).at(span)?;
+    let data = args.named::<Spanned<EcoString>>("data")?;
+    let value: serde_json::Value = if let Some(Spanned { v: data, span }) = data {
+        serde_json::from_str(&data)
+            .map_err(|err| format_json_error("json data", err))
+            .at(span)?
+    } else {
+        let Spanned { v: path, span } = args.expect::<Spanned<EcoString>>("path")?;
+        let path = vm.locate(&path).at(span)?;
+        let data = vm.world().file(&path).at(span)?;
+        serde_json::from_slice(&data)
+            .map_err(|err| format_json_error("json file", err))
+            .at(span)?
+    };
     convert_json(value)
 }
 
@@ -205,9 +230,141 @@ fn convert_json(value: serde_json::Value) -> Value {
 
 /// Format the user-facing JSON error message.
 #[track_caller]
-fn format_json_error(error: serde_json::Error) -> String {
+fn format_json_error(format: &str, error: serde_json::Error) -> String {
     assert!(error.is_syntax() || error.is_eof());
-    format!("failed to parse json file: syntax error in line {}", error.line())
+    format!("failed to parse {format}: syntax error in line {}", error.line())
+}
+
+/// Encode a value as pretty-printed JSON.
+///
+/// Arrays become JSON arrays, dictionaries become JSON objects, and `{none}`
+/// becomes `null`. Values without a JSON equivalent, like lengths or colors,
+/// are encoded as strings of their [representation]($func/repr), for example
+/// `{"12pt"}`. Floats that are not finite become `null`.
+///
+/// ## Example
+/// ```example
+/// #raw(
+///   pretty-json((name: "Typst", size: 12pt, tags: ("a", "b"))),
+///   lang: "json",
+/// )
+/// ```
+///
+/// Display: Pretty JSON
+/// Category: data-loading
+/// Returns: string
+#[func]
+fn encode_pretty_json(
+    /// The value to encode.
+    value: Value,
+) -> Value {
+    Value::Str(to_pretty_json(&value).into())
+}
+
+/// The `pretty-json` function.
+///
+/// The function macro derives names from the Rust identifier and can't
+/// produce a hyphen, so the name is set explicitly here.
+pub fn pretty_json() -> &'static NativeFunc {
+    static FUNC: NativeFunc = NativeFunc {
+        func: |vm, args| (encode_pretty_json().func)(vm, args),
+        info: Lazy::new(|| FuncInfo {
+            name: "pretty-json",
+            ..encode_pretty_json().info.clone()
+        }),
+    };
+    &FUNC
+}
+
+/// Encode a value as pretty-printed JSON.
+///
+/// Never fails: Values without a JSON equivalent are encoded as strings of
+/// their representation.
+pub fn to_pretty_json(value: &Value) -> String {
+    serde_json::to_string_pretty(&encode_json(value)).unwrap_or_default()
+}
+
+/// Convert a Typst value to a JSON value.
+fn encode_json(value: &Value) -> serde_json::Value {
+    match value {
+        Value::None => serde_json::Value::Null,
+        Value::Bool(v) => serde_json::Value::Bool(*v),
+        Value::Int(v) => serde_json::Value::from(*v),
+        Value::Float(v) => serde_json::Value::from(*v),
+        Value::Str(v) => serde_json::Value::String(v.as_str().into()),
+        Value::Array(v) => serde_json::Value::Array(v.iter().map(encode_json).collect()),
+        Value::Dict(v) => serde_json::Value::Object(
+            v.iter()
+                .map(|(key, value)| (key.as_str().into(), encode_json(value)))
+                .collect(),
+        ),
+        _ => serde_json::Value::String(value.repr().into()),
+    }
+}
+
+/// Read structured data from a JSON5 file.
+///
+/// JSON5 is a superset of JSON that is more convenient to write by hand: It
+/// allows comments, trailing commas, unquoted keys, single-quoted strings, and
+/// more. The file is converted into Typst values in the same way as with the
+/// [`json`]($func/json) function.
+///
+/// Instead of a path, you can also pass JSON5 source code directly through the
+/// `data` argument.
+///
+/// ## Example
+/// ```example
+/// #let config = json5(data: "{
+///   // The page title.
+///   title: 'Report',
+///   margins: [1, 2,],
+/// }")
+/// #config.title: #config.margins
+/// ```
+///
+/// Display: JSON5
+/// Category: data-loading
+/// Returns: array or dictionary
+#[cfg(feature = "json5")]
+#[func]
+pub fn json5(
+    /// Path to a JSON5 file.
+    ///
+    /// If this is given, `data` should not be given.
+    #[external]
+    path: EcoString,
+    /// JSON5 source code to parse instead of reading a file.
+    #[external]
+    #[named]
+    data: EcoString,
+) -> Value {
+    let data = args.named::<Spanned<EcoString>>("data")?;
+    let value: serde_json::Value = if let Some(Spanned { v: data, span }) = data {
+        json5::from_str(&data)
+            .map_err(|err| format_json5_error("json5 data", err))
+            .at(span)?
+    } else {
+        let Spanned { v: path, span } = args.expect::<Spanned<EcoString>>("path")?;
+        let path = vm.locate(&path).at(span)?;
+        let data = vm.world().file(&path).at(span)?;
+        let text = std::str::from_utf8(&data).map_err(FileError::from).at(span)?;
+        json5::from_str(text)
+            .map_err(|err| format_json5_error("json5 file", err))
+            .at(span)?
+    };
+    convert_json(value)
+}
+
+/// Format the user-facing JSON5 error message.
+#[cfg(feature = "json5")]
+fn format_json5_error(format: &str, error: json5::Error) -> String {
+    let json5::Error::Message { location, .. } = &error;
+    match location {
+        Some(location) => {
+            format!("failed to parse {format}: syntax error in line {}", location.line)
+        }
+        None => format!("failed to parse {format}: {error}"),
+    }
 }
 
 /// Read structured data from an XML file.
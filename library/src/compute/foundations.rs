@@ -1,3 +1,5 @@
+use typst::eval::{FuncInfo, Lazy, NativeFunc};
+
 use crate::prelude::*;
 
 /// Determine a value's type.
@@ -25,6 +27,53 @@ pub fn type_(
     value.type_name().into()
 }
 
+/// Cast a value to the first of the given types that accepts it.
+///
+/// Values that already have one of the types are returned unchanged. In
+/// addition, integers are accepted as floats, lengths and ratios as relative
+/// lengths, symbols as strings, and strings, symbols and `{none}` as content.
+///
+/// ## Example
+/// ```example
+/// #cast(5, "float") \
+/// #cast(50%, "length", "relative length") \
+/// #cast(auto, "length", "auto")
+/// ```
+///
+/// Display: Cast
+/// Category: foundations
+/// Returns: any
+#[func]
+pub fn cast(
+    /// The value to cast.
+    value: Value,
+    /// The names of the types to try, in order.
+    #[variadic]
+    types: Vec<Str>,
+) -> Value {
+    let Some(cast) = types.iter().find_map(|ty| cast_to(value.clone(), ty)) else {
+        bail!(
+            args.span,
+            "expected {}, found {}",
+            typst::util::separated_list(&types, "or"),
+            value.type_name(),
+        );
+    };
+    cast
+}
+
+/// Try to cast a value to the type with the given name.
+fn cast_to(value: Value, ty: &str) -> Option<Value> {
+    match ty {
+        "float" => cast_first_of!(value, f64).ok(),
+        "relative length" => cast_first_of!(value, Rel<Length>).ok(),
+        "string" => cast_first_of!(value, Str).ok(),
+        "content" => cast_first_of!(value, Content).ok(),
+        _ if value.type_name() == ty => Some(value),
+        _ => None,
+    }
+}
+
 /// The string representation of a value.
 ///
 /// When inserted into content, most values are displayed as this representation
@@ -50,6 +99,134 @@ pub fn repr(
     value.repr().into()
 }
 
+/// Show the differences between the representations of two values.
+///
+/// Compares the [representations]($func/repr) of the values line by line.
+/// Lines that only occur in the first value are prefixed with `-`, lines that
+/// only occur in the second one with `+`, and unchanged lines with two spaces.
+/// Since long arrays and dictionaries are represented with one item per line,
+/// this pinpoints the items that changed.
+///
+/// ## Example
+/// ```example
+/// #raw(diff(
+///   (name: "Typst", kind: "markup-based typesetter", year: 2023),
+///   (name: "Typst", kind: "markup-based typesetting system", year: 2023),
+/// ))
+/// ```
+///
+/// Display: Difference
+/// Category: foundations
+/// Returns: string
+#[func]
+pub fn diff(
+    /// The original value.
+    old: Value,
+    /// The changed value.
+    new: Value,
+) -> Value {
+    let (old, new) = (old.repr(), new.repr());
+    let a: Vec<&str> = old.lines().collect();
+    let b: Vec<&str> = new.lines().collect();
+    let mut lines = vec![];
+    diff_lines(&a, &b, &mut lines);
+    Value::Str(lines.join("\n").into())
+}
+
+/// Write a line diff between `a` and `b` into `out`.
+///
+/// Uses Hirschberg's algorithm to find a longest common subsequence, which
+/// takes quadratic time, but only linear space.
+fn diff_lines(mut a: &[&str], mut b: &[&str], out: &mut Vec<EcoString>) {
+    let prefix = a.iter().zip(b).take_while(|(x, y)| x == y).count();
+    out.extend(a[..prefix].iter().map(|line| eco_format!("  {line}")));
+    (a, b) = (&a[prefix..], &b[prefix..]);
+
+    let suffix = a.iter().rev().zip(b.iter().rev()).take_while(|(x, y)| x == y).count();
+    let common = &a[a.len() - suffix..];
+    (a, b) = (&a[..a.len() - suffix], &b[..b.len() - suffix]);
+
+    match a {
+        [] => out.extend(b.iter().map(|line| eco_format!("+ {line}"))),
+        [line] => match b.iter().position(|other| other == line) {
+            Some(k) => {
+                out.extend(b[..k].iter().map(|line| eco_format!("+ {line}")));
+                out.push(eco_format!("  {line}"));
+                out.extend(b[k + 1..].iter().map(|line| eco_format!("+ {line}")));
+            }
+            None => {
+                out.push(eco_format!("- {line}"));
+                out.extend(b.iter().map(|line| eco_format!("+ {line}")));
+            }
+        },
+        _ if b.is_empty() => out.extend(a.iter().map(|line| eco_format!("- {line}"))),
+        _ => {
+            // Split the first sequence in half and find the split of the second
+            // one that is part of a longest common subsequence.
+            let (a1, a2) = a.split_at(a.len() / 2);
+            let forward = lcs_lengths(a1.iter(), b.iter());
+            let backward = lcs_lengths(a2.iter().rev(), b.iter().rev());
+            let k = (0..=b.len())
+                .max_by_key(|&k| (forward[k] + backward[b.len() - k], usize::MAX - k))
+                .unwrap_or_default();
+            diff_lines(a1, &b[..k], out);
+            diff_lines(a2, &b[k..], out);
+        }
+    }
+
+    out.extend(common.iter().map(|line| eco_format!("  {line}")));
+}
+
+/// The lengths of the longest common subsequences of `a` and all prefixes of
+/// `b`.
+fn lcs_lengths<'a>(
+    a: impl Iterator<Item = &'a &'a str>,
+    b: impl Iterator<Item = &'a &'a str> + Clone,
+) -> Vec<usize> {
+    let mut prev = vec![0; b.clone().count() + 1];
+    let mut curr = prev.clone();
+    for x in a {
+        for (j, y) in b.clone().enumerate() {
+            curr[j + 1] = if x == y { prev[j] + 1 } else { prev[j + 1].max(curr[j]) };
+        }
+        std::mem::swap(&mut prev, &mut curr);
+    }
+    prev
+}
+
+/// Determine whether a collection is empty.
+///
+/// Works with arrays, dictionaries, strings and content. Content is only empty
+/// if it contains nothing at all: Content consisting of just a space is not
+/// empty.
+///
+/// ## Example
+/// ```example
+/// #let items = ()
+/// #if empty(items) [No items.]
+/// #empty("") \
+/// #empty((a: 1)) \
+/// #empty([ ])
+/// ```
+///
+/// Display: Empty
+/// Category: foundations
+/// Returns: boolean
+#[func]
+pub fn empty(
+    /// The collection to check.
+    value: Spanned<Value>,
+) -> Value {
+    let Some(empty) = value.v.is_empty() else {
+        bail!(
+            value.span,
+            "expected array, dictionary, string, or content, found {}",
+            value.v.type_name(),
+        );
+    };
+    Value::Bool(empty)
+}
+
 /// Fail with an error.
 ///
 /// ## Example
@@ -112,6 +289,196 @@ pub fn assert(
     Value::None
 }
 
+/// Return the given value unchanged.
+///
+/// This is useful as a default for parameters that expect a function.
+///
+/// ## Example
+/// ```example
+/// #let format(value, fmt: identity) = fmt(value)
+/// #format("plain") \
+/// #format("loud", fmt: upper)
+/// ```
+///
+/// Display: Identity
+/// Category: foundations
+/// Returns: any
+#[func]
+pub fn identity(
+    /// The value to return.
+    value: Value,
+) -> Value {
+    value
+}
+
+/// Create a function that always returns the same value.
+///
+/// The resulting function accepts and ignores any arguments.
+///
+/// ## Example
+/// ```example
+/// #(1, 2, 3).map(const("x")) \
+/// #let always-red = const(red)
+/// #text(fill: always-red(blue))[Red]
+/// ```
+///
+/// Display: Constant
+/// Category: foundations
+/// Returns: function
+#[func]
+pub fn const_(
+    /// The value the function should return.
+    value: Value,
+) -> Value {
+    Value::Func(Func::from(&CONST).with(Args::new(args.span, [value])))
+}
+
+/// The function returned by `const`. Returns its pre-applied value and
+/// ignores all other arguments.
+static CONST: NativeFunc = NativeFunc {
+    func: |_, args| {
+        let value: Value = args.expect("value")?;
+        args.take();
+        Ok(value)
+    },
+    info: Lazy::new(|| returned_func_info("const")),
+};
+
+/// Details for a function returned by the function with the given name.
+///
+/// Such functions are named after the function that created them and don't
+/// show up in the reference, so they have no further documentation.
+fn returned_func_info(name: &'static str) -> FuncInfo {
+    FuncInfo {
+        name,
+        display: name,
+        docs: "",
+        params: vec![],
+        returns: vec!["any"],
+        category: "foundations",
+    }
+}
+
+/// Turn a function of multiple arguments into a chain of functions that each
+/// take a single argument.
+///
+/// Once all arguments have been supplied one by one, the original function is
+/// called with all of them.
+///
+/// ## Example
+/// ```example
+/// #let add(x, y) = x + y
+/// #let increment = curry(add)(1)
+/// #increment(2) \
+/// #(1, 2, 3).map(curry(add)(10))
+/// ```
+///
+/// Display: Curry
+/// Category: foundations
+/// Returns: function
+#[func]
+pub fn curry(
+    /// The function to curry.
+    func: Func,
+    /// How many arguments the function takes. Can be omitted for closures
+    /// with a fixed number of positional parameters.
+    #[default]
+    arity: Option<NonZeroUsize>,
+) -> Value {
+    let Some(arity) = arity.or_else(|| func.argc().and_then(NonZeroUsize::new)) else {
+        bail!(args.span, "cannot determine the number of arguments, please specify it");
+    };
+    curried_func(func, arity.get(), args.span)
+}
+
+/// Create a function that expects the remaining arguments one by one.
+fn curried_func(func: Func, arity: usize, span: Span) -> Value {
+    let args = Args::new(span, [Value::Func(func), Value::Int(arity as i64)]);
+    Value::Func(Func::from(&CURRY).with(args))
+}
+
+/// The function returned by `curry`. Supplies one argument to the curried
+/// function and calls it once all arguments are supplied.
+static CURRY: NativeFunc = NativeFunc {
+    func: |vm, args| {
+        let func: Func = args.expect("function")?;
+        let arity: usize = args.expect("arity")?;
+        let arg: Value = args.expect("argument")?;
+        let func = func.with(Args::new(args.span, [arg]));
+        if arity > 1 {
+            Ok(curried_func(func, arity - 1, args.span))
+        } else {
+            func.call_vm(vm, Args::new(args.span, []))
+        }
+    },
+    info: Lazy::new(|| returned_func_info("curry")),
+};
+
+/// Compose two functions.
+///
+/// The resulting function takes a single argument, applies the second
+/// function to it and then applies the first function to the result.
+///
+/// ## Example
+/// ```example
+/// #let double(x) = 2 * x
+/// #let increment(x) = x + 1
+/// #compose(double, increment)(3) \
+/// #compose(increment, double)(3)
+/// ```
+///
+/// Display: Compose
+/// Category: foundations
+/// Returns: function
+#[func]
+pub fn compose(
+    /// The function to apply last.
+    outer: Func,
+    /// The function to apply first.
+    inner: Func,
+) -> Value {
+    let args = Args::new(args.span, [Value::Func(outer), Value::Func(inner)]);
+    Value::Func(Func::from(&COMPOSE).with(args))
+}
+
+/// The function returned by `compose`. Applies the inner and then the outer
+/// function.
+static COMPOSE: NativeFunc = NativeFunc {
+    func: |vm, args| {
+        let outer: Func = args.expect("outer")?;
+        let inner: Func = args.expect("inner")?;
+        let value: Value = args.expect("value")?;
+        let value = inner.call_vm(vm, Args::new(args.span, [value]))?;
+        outer.call_vm(vm, Args::new(args.span, [value]))
+    },
+    info: Lazy::new(|| returned_func_info("compose")),
+};
+
+/// Return a value unless it is `{none}`, in which case the fallback is
+/// returned instead.
+///
+/// This is useful for optional parameters that default to `{none}`.
+///
+/// ## Example
+/// ```example
+/// #let greet(name: none) = [Hello, #default(name, "stranger")!]
+/// #greet() \
+/// #greet(name: "Alice")
+/// ```
+///
+/// Display: Default
+/// Category: foundations
+/// Returns: any
+#[func]
+pub fn default(
+    /// The value to return if it is not `{none}`.
+    value: Value,
+    /// The value to return if `value` is `{none}`.
+    fallback: Value,
+) -> Value {
+    typst::eval::coalesce(value, || fallback)
+}
+
 /// Evaluate a string as Typst code.
 ///
 /// This function should only be used as a last resort.
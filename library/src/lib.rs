@@ -37,6 +37,7 @@ fn global(math: Module, calc: Module) -> Module {
     global.define("emph", text::EmphElem::func());
     global.define("lower", text::lower);
     global.define("upper", text::upper);
+    global.define("title", text::title);
     global.define("smallcaps", text::smallcaps);
     global.define("sub", text::SubElem::func());
     global.define("super", text::SuperElem::func());
@@ -108,6 +109,14 @@ fn global(math: Module, calc: Module) -> Module {
     // Compute.
     global.define("type", compute::type_);
     global.define("repr", compute::repr);
+    global.define("cast", compute::cast);
+    global.define("diff", compute::diff);
+    global.define("empty", compute::empty);
+    global.define("identity", compute::identity);
+    global.define("const", compute::const_);
+    global.define("curry", compute::curry);
+    global.define("compose", compute::compose);
+    global.define("default", compute::default);
     global.define("panic", compute::panic);
     global.define("assert", compute::assert);
     global.define("eval", compute::eval);
@@ -115,15 +124,23 @@ fn global(math: Module, calc: Module) -> Module {
     global.define("float", compute::float);
     global.define("luma", compute::luma);
     global.define("rgb", compute::rgb);
+    global.define("hsl", compute::hsl);
     global.define("cmyk", compute::cmyk);
+    global.define("mix", compute::mix);
     global.define("symbol", compute::symbol);
     global.define("str", compute::str);
+    global.define("array", compute::array);
     global.define("label", compute::label);
     global.define("regex", compute::regex);
     global.define("range", compute::range);
+    global.define("linspace", compute::linspace);
+    global.define("arange", compute::arange);
     global.define("read", compute::read);
     global.define("csv", compute::csv);
     global.define("json", compute::json);
+    global.define("pretty-json", compute::pretty_json);
+    #[cfg(feature = "json5")]
+    global.define("json5", compute::json5);
     global.define("xml", compute::xml);
 
     // Calc.
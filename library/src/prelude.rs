@@ -15,8 +15,8 @@ pub use typst::diag::{bail, error, At, SourceResult, StrResult};
 pub use typst::doc::*;
 #[doc(no_inline)]
 pub use typst::eval::{
-    array, cast_from_value, cast_to_value, dict, format_str, func, Args, Array, Cast,
-    CastInfo, Dict, Func, Never, Str, Symbol, Value, Vm,
+    array, cast_first_of, cast_from_value, cast_to_value, dict, format_str, func, Args,
+    Array, Cast, CastInfo, Dict, Func, Never, Str, Symbol, Value, Vm,
 };
 #[doc(no_inline)]
 pub use typst::geom::*;
@@ -225,6 +225,42 @@ pub fn upper(
     case(text, Case::Upper)
 }
 
+/// Convert a string to title case.
+///
+/// Uppercases the first letter of each word and leaves all other letters as
+/// they are. Words are separated by whitespace.
+///
+/// Unlike [`upper`]($func/upper) and [`lower`]($func/lower), this only works
+/// on strings because word boundaries can't be reliably detected across
+/// styled pieces of content.
+///
+/// ## Example
+/// ```example
+/// #title("the quick brown fox") \
+/// #title("éléphant rose")
+/// ```
+///
+/// Display: Title case
+/// Category: text
+/// Returns: string
+#[func]
+pub fn title(
+    /// The string to convert to title case.
+    text: Str,
+) -> Value {
+    let mut title = String::with_capacity(text.as_str().len());
+    let mut word_start = true;
+    for c in text.chars() {
+        if word_start {
+            title.extend(c.to_uppercase());
+        } else {
+            title.push(c);
+        }
+        word_start = c.is_whitespace();
+    }
+    Value::Str(title.into())
+}
+
 /// Change the case of text.
 fn case(text: ToCase, case: Case) -> Value {
     match text {
@@ -46,6 +46,21 @@ impl Args {
         })
     }
 
+    /// Push a named argument.
+    pub fn push_named(&mut self, span: Span, name: impl Into<Str>, value: Value) {
+        self.items.push(Arg {
+            span: self.span,
+            name: Some(name.into()),
+            value: Spanned::new(value, span),
+        })
+    }
+
+    /// Add a named argument, builder-style.
+    pub fn with_named(mut self, name: impl Into<Str>, value: impl Into<Value>) -> Self {
+        self.push_named(self.span, name, value.into());
+        self
+    }
+
     /// Consume and cast the first positional argument if there is one.
     pub fn eat<T>(&mut self) -> SourceResult<Option<T>>
     where
@@ -187,3 +202,82 @@ impl Debug for Arg {
         Debug::fmt(&self.value.v, f)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_args_builder() {
+        let span = Span::detached();
+        let mut args = Args::new(span, [Value::Int(1), Value::Int(2)])
+            .with_named("sep", "-")
+            .with_named("last", false);
+        args.push(span, Value::Int(3));
+
+        assert_eq!(format!("{args:?}"), "(1, 2, sep: \"-\", last: false, 3)");
+        assert_eq!(args.named::<bool>("last").unwrap(), Some(false));
+        assert_eq!(args.all::<i64>().unwrap(), vec![1, 2, 3]);
+        assert_eq!(
+            args.expect::<Str>("separator").unwrap_err()[0].message,
+            "missing argument: separator"
+        );
+        assert_eq!(args.named::<Str>("sep").unwrap(), Some("-".into()));
+        assert!(args.finish().is_ok());
+    }
+
+    #[test]
+    fn test_args_expect() {
+        let span = Span::detached();
+        let mut args = Args::new(span, [Value::Int(1), Value::Str("a".into())]);
+        assert_eq!(args.expect::<i64>("count").unwrap(), 1);
+
+        let error = args.expect::<i64>("count").unwrap_err();
+        assert_eq!(error[0].message, "expected integer, found string");
+        assert!(args.items.is_empty());
+
+        let error = args.expect::<i64>("count").unwrap_err();
+        assert_eq!(error[0].message, "missing argument: count");
+    }
+
+    #[test]
+    fn test_args_named() {
+        let span = Span::detached();
+        let mut args = Args::new(span, [])
+            .with_named("size", 1)
+            .with_named("fill", "red")
+            .with_named("size", 2);
+        assert_eq!(args.named::<i64>("size").unwrap(), Some(2));
+        assert_eq!(args.named::<i64>("size").unwrap(), None);
+        assert_eq!(args.named::<i64>("stroke").unwrap(), None);
+
+        let error = args.named::<i64>("fill").unwrap_err();
+        assert_eq!(error[0].message, "expected integer, found string");
+        assert!(args.finish().is_ok());
+
+        let args = Args::new(span, []).with_named("extra", true);
+        assert_eq!(args.finish().unwrap_err()[0].message, "unexpected argument");
+    }
+
+    #[test]
+    fn test_args_all() {
+        let span = Span::detached();
+        let mut args = Args::new(span, [Value::Int(1), Value::Str("a".into())])
+            .with_named("fill", "red");
+        args.push(span, Value::Float(2.5));
+
+        let values: Vec<_> = args
+            .all::<Spanned<Value>>()
+            .unwrap()
+            .into_iter()
+            .map(|value| value.v)
+            .collect();
+        assert_eq!(
+            values,
+            vec![Value::Int(1), Value::Str("a".into()), Value::Float(2.5)]
+        );
+        assert_eq!(args.to_pos(), Array::new());
+        assert_eq!(args.named::<Str>("fill").unwrap(), Some("red".into()));
+        assert!(args.finish().is_ok());
+    }
+}
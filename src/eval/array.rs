@@ -1,11 +1,13 @@
 use std::cmp::Ordering;
 use std::fmt::{self, Debug, Formatter};
+use std::num::NonZeroUsize;
 use std::ops::{Add, AddAssign};
 
 use ecow::{eco_format, EcoString, EcoVec};
 
-use super::{ops, Args, Func, Value, Vm};
+use super::{ops, Args, Func, Str, Value, Vm};
 use crate::diag::{At, SourceResult, StrResult};
+use crate::syntax::Span;
 use crate::util::pretty_array_like;
 
 /// Create a new [`Array`] from values.
@@ -137,6 +139,26 @@ impl Array {
         self.0.contains(value)
     }
 
+    /// Whether every value in this array also occurs in another one.
+    ///
+    /// With `multiset`, values that occur multiple times in this array must
+    /// occur at least as often in the other one.
+    pub fn is_subset(&self, other: &Array, multiset: bool) -> bool {
+        if !multiset {
+            return self.iter().all(|value| other.contains(value));
+        }
+
+        let mut remaining: Vec<&Value> = other.iter().collect();
+        for value in self.iter() {
+            let Some(i) = remaining.iter().position(|&v| v == value) else {
+                return false;
+            };
+            remaining.swap_remove(i);
+        }
+
+        true
+    }
+
     /// Return the first matching item.
     pub fn find(&self, vm: &mut Vm, func: Func) -> SourceResult<Option<Value>> {
         for item in self.iter() {
@@ -160,6 +182,38 @@ impl Array {
         Ok(None)
     }
 
+    /// Return the first matching item together with its index.
+    pub fn first_where(
+        &self,
+        vm: &mut Vm,
+        func: Func,
+    ) -> SourceResult<Option<(Value, i64)>> {
+        for (i, item) in self.iter().enumerate() {
+            let args = Args::new(func.span(), [item.clone()]);
+            if func.call_vm(vm, args)?.cast::<bool>().at(func.span())? {
+                return Ok(Some((item.clone(), i as i64)));
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Return the last matching item together with its index.
+    pub fn last_where(
+        &self,
+        vm: &mut Vm,
+        func: Func,
+    ) -> SourceResult<Option<(Value, i64)>> {
+        for (i, item) in self.iter().enumerate().rev() {
+            let args = Args::new(func.span(), [item.clone()]);
+            if func.call_vm(vm, args)?.cast::<bool>().at(func.span())? {
+                return Ok(Some((item.clone(), i as i64)));
+            }
+        }
+
+        Ok(None)
+    }
+
     /// Return a new array with only those items for which the function returns
     /// true.
     pub fn filter(&self, vm: &mut Vm, func: Func) -> SourceResult<Self> {
@@ -224,18 +278,74 @@ impl Array {
     }
 
     /// Return a new array with all items from this and nested arrays.
-    pub fn flatten(&self) -> Self {
+    ///
+    /// With a `depth`, only that many levels of nesting are removed.
+    pub fn flatten(&self, depth: Option<usize>) -> Self {
         let mut flat = EcoVec::with_capacity(self.0.len());
         for item in self.iter() {
-            if let Value::Array(nested) = item {
-                flat.extend(nested.flatten().into_iter());
-            } else {
-                flat.push(item.clone());
+            match (item, depth) {
+                (Value::Array(_), Some(0)) => flat.push(item.clone()),
+                (Value::Array(nested), _) => {
+                    flat.extend(nested.flatten(depth.map(|d| d - 1)).into_iter())
+                }
+                _ => flat.push(item.clone()),
             }
         }
         Self::from_vec(flat)
     }
 
+    /// Pair up the items of this and another array.
+    ///
+    /// The result is as long as the shorter of the two arrays.
+    pub fn zip(&self, other: &Array) -> Self {
+        self.iter()
+            .zip(other.iter())
+            .map(|(a, b)| Value::Array(array![a.clone(), b.clone()]))
+            .collect()
+    }
+
+    /// Pair up each item with its index, counting from `start`.
+    pub fn enumerate(&self, start: i64) -> StrResult<Self> {
+        self.iter()
+            .enumerate()
+            .map(|(i, item)| {
+                let index = i64::try_from(i)
+                    .ok()
+                    .and_then(|i| start.checked_add(i))
+                    .ok_or("array index is too large")?;
+                Ok(Value::Array(array![index, item.clone()]))
+            })
+            .collect()
+    }
+
+    /// Split an array of equally long arrays into one array per position.
+    pub fn unzip(&self) -> StrResult<Self> {
+        let mut columns: Option<Vec<EcoVec<Value>>> = None;
+        for item in self.iter() {
+            let Value::Array(tuple) = item else {
+                return Err(eco_format!("expected array, found {}", item.type_name()));
+            };
+            let columns = columns.get_or_insert_with(|| {
+                vec![EcoVec::with_capacity(self.0.len()); tuple.0.len()]
+            });
+            if tuple.0.len() != columns.len() {
+                return Err(eco_format!(
+                    "array lengths differ (expected {}, found {})",
+                    columns.len(),
+                    tuple.0.len(),
+                ));
+            }
+            for (column, value) in columns.iter_mut().zip(tuple.iter()) {
+                column.push(value.clone());
+            }
+        }
+        Ok(columns
+            .unwrap_or_default()
+            .into_iter()
+            .map(|column| Value::Array(Self::from_vec(column)))
+            .collect())
+    }
+
     /// Returns a new array with reversed order.
     pub fn rev(&self) -> Self {
         self.0.iter().cloned().rev().collect()
@@ -249,6 +359,26 @@ impl Array {
             .collect()
     }
 
+    /// Split the array into consecutive chunks of the given size.
+    ///
+    /// The last chunk is shorter if the length is not divisible by the size.
+    pub fn chunks(&self, size: NonZeroUsize) -> Array {
+        self.as_slice()
+            .chunks(size.get())
+            .map(|chunk| Value::Array(chunk.iter().cloned().collect()))
+            .collect()
+    }
+
+    /// All overlapping windows of the given size.
+    ///
+    /// Empty if the array is shorter than the window size.
+    pub fn windows(&self, size: NonZeroUsize) -> Array {
+        self.as_slice()
+            .windows(size.get())
+            .map(|window| Value::Array(window.iter().cloned().collect()))
+            .collect()
+    }
+
     /// Join all values in the array, optionally with separator and last
     /// separator (between the final two items).
     pub fn join(&self, sep: Option<Value>, mut last: Option<Value>) -> StrResult<Value> {
@@ -271,6 +401,68 @@ impl Array {
         Ok(result)
     }
 
+    /// Add up all items, optionally mapping them with a key function first.
+    ///
+    /// An empty array sums up to zero.
+    pub fn sum(&self, vm: &mut Vm, span: Span, key: Option<Func>) -> SourceResult<Value> {
+        self.reduce(vm, span, key, ops::add)
+            .map(|v| v.unwrap_or(Value::Int(0)))
+    }
+
+    /// Multiply all items, optionally mapping them with a key function first.
+    ///
+    /// The product of an empty array is one.
+    pub fn product(
+        &self,
+        vm: &mut Vm,
+        span: Span,
+        key: Option<Func>,
+    ) -> SourceResult<Value> {
+        self.reduce(vm, span, key, ops::mul)
+            .map(|v| v.unwrap_or(Value::Int(1)))
+    }
+
+    /// Combine all (mapped) items with a binary operator, from left to right.
+    fn reduce(
+        &self,
+        vm: &mut Vm,
+        span: Span,
+        key: Option<Func>,
+        op: fn(Value, Value) -> StrResult<Value>,
+    ) -> SourceResult<Option<Value>> {
+        let mut acc = None;
+        for item in self.iter() {
+            let item = match &key {
+                Some(func) => func.call_vm(vm, Args::new(func.span(), [item.clone()]))?,
+                None => item.clone(),
+            };
+            acc = Some(match acc {
+                Some(acc) => op(acc, item).at(span)?,
+                None => item,
+            });
+        }
+        Ok(acc)
+    }
+
+    /// Remove duplicate items, keeping the first occurrence of each.
+    ///
+    /// With a key function, items whose keys are equal count as duplicates.
+    pub fn unique(&self, vm: &mut Vm, key: Option<Func>) -> SourceResult<Self> {
+        let mut keys = Vec::with_capacity(self.0.len());
+        let mut vec = EcoVec::new();
+        for item in self.iter() {
+            let key = match &key {
+                Some(func) => func.call_vm(vm, Args::new(func.span(), [item.clone()]))?,
+                None => item.clone(),
+            };
+            if !keys.contains(&key) {
+                keys.push(key);
+                vec.push(item.clone());
+            }
+        }
+        Ok(Self::from_vec(vec))
+    }
+
     /// Return a sorted version of this array.
     ///
     /// Returns an error if two values could not be compared.
@@ -292,6 +484,99 @@ impl Array {
         result.map(|_| Self::from_vec(vec))
     }
 
+    /// Replace each item with the maximum (for `Ordering::Greater`) or minimum
+    /// (for `Ordering::Less`) of it and all preceding items.
+    ///
+    /// Returns an error if two values could not be compared.
+    pub fn running(&self, goal: Ordering) -> StrResult<Self> {
+        let mut extremum: Option<&Value> = None;
+        let mut running = EcoVec::with_capacity(self.0.len());
+        for item in self.iter() {
+            let current = match extremum {
+                Some(extremum) => match item.partial_cmp(extremum) {
+                    Some(ordering) if ordering == goal => item,
+                    Some(_) => extremum,
+                    None => {
+                        return Err(eco_format!(
+                            "cannot compare {} and {}",
+                            extremum.type_name(),
+                            item.type_name(),
+                        ))
+                    }
+                },
+                None => item,
+            };
+            extremum = Some(current);
+            running.push(current.clone());
+        }
+        Ok(Self::from_vec(running))
+    }
+
+    /// The longest common prefix (or suffix) of an array of strings.
+    ///
+    /// Works on Unicode scalar values, so a multi-byte character is never
+    /// split. Returns an error if the array is empty or contains non-strings.
+    pub fn common_affix(&self, suffix: bool) -> StrResult<Str> {
+        let mut iter = self.iter();
+        let first: Str = iter.next().ok_or_else(array_is_empty)?.clone().cast()?;
+        let mut affix = first.as_str();
+        for item in iter {
+            let item: Str = item.clone().cast()?;
+            affix = if suffix {
+                let len = common_len(affix.chars().rev(), item.chars().rev());
+                &affix[affix.len() - len..]
+            } else {
+                &affix[..common_len(affix.chars(), item.chars())]
+            };
+        }
+        Ok(affix.into())
+    }
+
+    /// Take the items at the given indices, in the order of the indices.
+    pub fn reorder(&self, indices: &Array) -> StrResult<Self> {
+        indices
+            .iter()
+            .map(|index| self.at(index.clone().cast()?).cloned())
+            .collect()
+    }
+
+    /// The indices that would sort the array, optionally comparing the results
+    /// of a key function instead of the items themselves.
+    ///
+    /// Items that compare equal keep their relative order.
+    pub fn argsort(
+        &self,
+        vm: &mut Vm,
+        span: Span,
+        key: Option<Func>,
+    ) -> SourceResult<Self> {
+        let keys = match key {
+            Some(func) => self
+                .iter()
+                .map(|item| func.call_vm(vm, Args::new(func.span(), [item.clone()])))
+                .collect::<SourceResult<Vec<_>>>()?,
+            None => self.0.to_vec(),
+        };
+
+        let mut result = Ok(());
+        let mut indices: Vec<usize> = (0..keys.len()).collect();
+        indices.sort_by(|&a, &b| {
+            keys[a].partial_cmp(&keys[b]).unwrap_or_else(|| {
+                if result.is_ok() {
+                    result = Err(eco_format!(
+                        "cannot order {} and {}",
+                        keys[a].type_name(),
+                        keys[b].type_name(),
+                    ));
+                }
+                Ordering::Equal
+            })
+        });
+        result.at(span)?;
+
+        Ok(indices.into_iter().map(|i| Value::Int(i as i64)).collect())
+    }
+
     /// Repeat this array `n` times.
     pub fn repeat(&self, n: i64) -> StrResult<Self> {
         let count = usize::try_from(n)
@@ -371,6 +656,12 @@ impl<'a> IntoIterator for &'a Array {
     }
 }
 
+/// The number of bytes taken up by the characters the two sequences share
+/// at their start.
+fn common_len(a: impl Iterator<Item = char>, b: impl Iterator<Item = char>) -> usize {
+    a.zip(b).take_while(|(x, y)| x == y).map(|(c, _)| c.len_utf8()).sum()
+}
+
 /// The error message when the array is empty.
 #[cold]
 fn array_is_empty() -> EcoString {
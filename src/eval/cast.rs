@@ -10,6 +10,29 @@ use crate::diag::StrResult;
 use crate::syntax::Spanned;
 use crate::util::separated_list;
 
+/// Cast a value to the first of the given types that accepts it and turn the
+/// result back into a value.
+///
+/// Fails with an error listing all types if none of them accepts the value.
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __cast_first_of {
+    ($value:expr, $($ty:ty),+ $(,)?) => {{
+        let value: $crate::eval::Value = $value;
+        $(if <$ty as $crate::eval::Cast>::is(&value) {
+            <$ty as $crate::eval::Cast>::cast(value).map($crate::eval::Value::from)
+        } else)+ {
+            let info = $crate::eval::CastInfo::Union(vec![
+                $(<$ty as $crate::eval::Cast>::describe()),+
+            ]);
+            Err(info.error(&value))
+        }
+    }};
+}
+
+#[doc(inline)]
+pub use crate::__cast_first_of as cast_first_of;
+
 /// Cast from a value to a specific type.
 pub trait Cast<V = Value>: Sized {
     /// Check whether the value is castable to `Self`.
@@ -5,7 +5,7 @@ use std::sync::Arc;
 
 use ecow::{eco_format, EcoString};
 
-use super::{array, Array, Str, Value};
+use super::{array, Array, Cast, Str, Value};
 use crate::diag::StrResult;
 use crate::syntax::is_ident;
 use crate::util::{pretty_array_like, separated_list, ArcExt};
@@ -74,6 +74,11 @@ impl Dict {
         self.0.contains_key(key)
     }
 
+    /// Whether every pair in this dictionary also occurs in another one.
+    pub fn is_subset(&self, other: &Dict) -> bool {
+        self.iter().all(|(key, value)| other.0.get(key) == Some(value))
+    }
+
     /// Insert a mapping from the given `key` to the given `value`.
     pub fn insert(&mut self, key: Str, value: Value) {
         Arc::make_mut(&mut self.0).insert(key, value);
@@ -101,6 +106,28 @@ impl Dict {
         self.0.keys().cloned().map(Value::Str).collect()
     }
 
+    /// Recursively merge another dictionary into this one.
+    ///
+    /// Dictionaries under the same key are merged in turn and arrays under
+    /// the same key are combined according to the given strategy. All other
+    /// values from `other` replace the existing ones.
+    pub fn merge(&mut self, other: Dict, arrays: ArrayStrategy) {
+        let map = Arc::make_mut(&mut self.0);
+        for (key, value) in other {
+            let merged = match (map.remove(&key), value) {
+                (Some(Value::Dict(mut dict)), Value::Dict(other)) => {
+                    dict.merge(other, arrays);
+                    Value::Dict(dict)
+                }
+                (Some(Value::Array(array)), Value::Array(other)) => {
+                    Value::Array(arrays.combine(array, other))
+                }
+                (_, value) => value,
+            };
+            map.insert(key, merged);
+        }
+    }
+
     /// Return the values of the dictionary as an array.
     pub fn values(&self) -> Array {
         self.0.values().cloned().collect()
@@ -153,6 +180,38 @@ impl Debug for Dict {
     }
 }
 
+/// How arrays under the same key are combined when merging dictionaries.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash, Cast)]
+pub enum ArrayStrategy {
+    /// The new array replaces the existing one.
+    #[default]
+    Replace,
+    /// The new array is appended to the existing one.
+    Concat,
+    /// Like `concat`, but values that occur multiple times are only kept
+    /// once.
+    Union,
+}
+
+impl ArrayStrategy {
+    /// Combine two arrays according to this strategy.
+    fn combine(self, array: Array, other: Array) -> Array {
+        match self {
+            Self::Replace => other,
+            Self::Concat => array + other,
+            Self::Union => {
+                let mut union = Array::new();
+                for value in array.into_iter().chain(other) {
+                    if !union.contains(&value) {
+                        union.push(value);
+                    }
+                }
+                union
+            }
+        }
+    }
+}
+
 impl Add for Dict {
     type Output = Self;
 
@@ -10,7 +10,7 @@ use once_cell::sync::Lazy;
 use super::{
     cast_to_value, Args, CastInfo, Eval, Flow, Route, Scope, Scopes, Tracer, Value, Vm,
 };
-use crate::diag::{bail, SourceResult};
+use crate::diag::{bail, At, SourceResult};
 use crate::model::{ElemFunc, Introspector, StabilityProvider, Vt};
 use crate::syntax::ast::{self, AstNode, Expr, Ident};
 use crate::syntax::{SourceId, Span, SyntaxNode};
@@ -36,6 +36,8 @@ enum Repr {
     Closure(Arc<Prehashed<Closure>>),
     /// A nested function with pre-applied arguments.
     With(Arc<(Func, Args)>),
+    /// A nested predicate whose result is inverted.
+    Complement(Arc<Func>),
 }
 
 impl Func {
@@ -46,6 +48,7 @@ impl Func {
             Repr::Elem(func) => Some(func.info().name),
             Repr::Closure(closure) => closure.name.as_deref(),
             Repr::With(arc) => arc.0.name(),
+            Repr::Complement(_) => None,
         }
     }
 
@@ -56,6 +59,7 @@ impl Func {
             Repr::Elem(func) => Some(func.info()),
             Repr::Closure(_) => None,
             Repr::With(arc) => arc.0.info(),
+            Repr::Complement(_) => None,
         }
     }
 
@@ -79,6 +83,7 @@ impl Func {
             Repr::With(arc) => Some(arc.0.argc()?.saturating_sub(
                 arc.1.items.iter().filter(|arg| arg.name.is_none()).count(),
             )),
+            Repr::Complement(inner) => inner.argc(),
             _ => None,
         }
     }
@@ -117,6 +122,10 @@ impl Func {
                 args.items = arc.1.items.iter().cloned().chain(args.items).collect();
                 arc.0.call_vm(vm, args)
             }
+            Repr::Complement(inner) => {
+                let value = inner.call_vm(vm, args)?;
+                Ok(Value::Bool(!value.cast::<bool>().at(self.span)?))
+            }
         }
     }
 
@@ -140,6 +149,13 @@ impl Func {
         Self { repr: Repr::With(Arc::new((self, args))), span }
     }
 
+    /// Create a predicate that returns `true` whenever this one returns
+    /// `false` and vice versa.
+    pub fn complement(self) -> Self {
+        let span = self.span;
+        Self { repr: Repr::Complement(Arc::new(self)), span }
+    }
+
     /// Extract the element function, if it is one.
     pub fn element(&self) -> Option<ElemFunc> {
         match self.repr {
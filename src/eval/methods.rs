@@ -1,9 +1,13 @@
 //! Methods on values.
 
+use std::cmp::Ordering;
+use std::num::NonZeroUsize;
+
 use ecow::EcoString;
 
-use super::{Args, Str, Value, Vm};
+use super::{array, ops, Args, Array, Dict, Str, Value, Vm};
 use crate::diag::{At, SourceResult};
+use crate::geom::{AbsUnit, Length, Rel};
 use crate::model::Location;
 use crate::syntax::Span;
 
@@ -23,9 +27,50 @@ pub fn call(
             "lighten" => Value::Color(color.lighten(args.expect("amount")?)),
             "darken" => Value::Color(color.darken(args.expect("amount")?)),
             "negate" => Value::Color(color.negate()),
+            "with-alpha" => {
+                Value::Color(color.to_rgba().with_alpha(args.expect("alpha")?).into())
+            }
+            "fade" => Value::Color(color.to_rgba().fade(args.expect("factor")?).into()),
+            "premultiply" => Value::Color(color.to_rgba().premultiply().into()),
+            "unpremultiply" => Value::Color(color.to_rgba().unpremultiply().into()),
+            "hue" => Value::Angle(color.to_rgba().to_hsl().0),
+            "saturation" => Value::Ratio(color.to_rgba().to_hsl().1),
+            "lightness" => Value::Ratio(color.to_rgba().to_hsl().2),
+            "to-hex" => Value::Str(color.to_rgba().to_hex().into()),
+            "to-rgb-string" => Value::Str(color.to_rgba().to_rgb_string().into()),
+            _ => return missing(),
+        },
+
+        Value::Length(length)
+            if matches!(method, "to-pt" | "to-mm" | "to-cm" | "to-in" | "convert") =>
+        {
+            let unit = match method {
+                "to-pt" => AbsUnit::Pt,
+                "to-mm" => AbsUnit::Mm,
+                "to-cm" => AbsUnit::Cm,
+                "to-in" => AbsUnit::In,
+                _ => args.expect("unit")?,
+            };
+            Value::Float(length.try_to_unit(unit).at(span)?)
+        }
+
+        Value::Angle(angle) => match method {
+            "to-deg" => Value::Float(angle.to_deg()),
+            "to-rad" => Value::Float(angle.to_rad()),
+            "normalize" => Value::Angle(angle.normalized()),
             _ => return missing(),
         },
 
+        Value::Length(_) | Value::Ratio(_) | Value::Relative(_) => {
+            let relative: Rel<Length> = value.cast().at(span)?;
+            match method {
+                "rel-part" => Value::Ratio(relative.rel),
+                "abs-part" => Value::Length(relative.abs),
+                "resolve" => Value::Length(relative.relative_to(args.expect("base")?)),
+                _ => return missing(),
+            }
+        }
+
         Value::Str(string) => match method {
             "len" => Value::Int(string.len()),
             "first" => Value::Str(string.first().at(span)?),
@@ -41,6 +86,11 @@ pub fn call(
             }
             "clusters" => Value::Array(string.clusters()),
             "codepoints" => Value::Array(string.codepoints()),
+            "count-graphemes" => Value::Int(string.count_graphemes()),
+            "rev" => Value::Str(string.rev()),
+            "is-numeric" => Value::Bool(string.all_chars(char::is_numeric)),
+            "is-alpha" => Value::Bool(string.all_chars(char::is_alphabetic)),
+            "is-alphanumeric" => Value::Bool(string.all_chars(char::is_alphanumeric)),
             "contains" => Value::Bool(string.contains(args.expect("pattern")?)),
             "starts-with" => Value::Bool(string.starts_with(args.expect("pattern")?)),
             "ends-with" => Value::Bool(string.ends_with(args.expect("pattern")?)),
@@ -60,6 +110,9 @@ pub fn call(
                 let count = args.named("count")?;
                 Value::Str(string.replace(pattern, with, count))
             }
+            "replace-map" => {
+                Value::Str(string.replace_map(&args.expect("mapping")?).at(span)?)
+            }
             "trim" => {
                 let pattern = args.eat()?;
                 let at = args.named("at")?;
@@ -67,6 +120,20 @@ pub fn call(
                 Value::Str(string.trim(pattern, at, repeat))
             }
             "split" => Value::Array(string.split(args.eat()?)),
+            "mask" => {
+                let keep_start = args.named("keep-start")?.unwrap_or(0);
+                let keep_end = args.named("keep-end")?.unwrap_or(0);
+                let with: Option<Str> = args.named("mask-char")?;
+                Value::Str(string.mask(
+                    keep_start,
+                    keep_end,
+                    with.as_deref().unwrap_or("*"),
+                ))
+            }
+            "expand-tabs" => {
+                let width = args.named("width")?.unwrap_or(NonZeroUsize::new(4).unwrap());
+                Value::Str(string.expand_tabs(width))
+            }
             _ => return missing(),
         },
 
@@ -74,6 +141,7 @@ pub fn call(
             "func" => content.func().into(),
             "has" => Value::Bool(content.has(&args.expect::<EcoString>("field")?)),
             "at" => content.at(&args.expect::<EcoString>("field")?).at(span)?,
+            "count" => Value::Int(content.count(&args.expect("selector")?) as i64),
             "location" => content
                 .location()
                 .ok_or("this method can only be called on content returned by query(..)")
@@ -96,10 +164,25 @@ pub fn call(
                 Value::Array(array.slice(start, end).at(span)?)
             }
             "contains" => Value::Bool(array.contains(&args.expect("value")?)),
+            "is-subset" | "is-superset" => {
+                let other: Array = args.expect("other")?;
+                let multiset = args.named("multiset")?.unwrap_or(false);
+                Value::Bool(if method == "is-subset" {
+                    array.is_subset(&other, multiset)
+                } else {
+                    other.is_subset(&array, multiset)
+                })
+            }
             "find" => array.find(vm, args.expect("function")?)?.unwrap_or(Value::None),
             "position" => array
                 .position(vm, args.expect("function")?)?
                 .map_or(Value::None, Value::Int),
+            "first-where" => array
+                .first_where(vm, args.expect("function")?)?
+                .map_or(Value::None, |(v, i)| Value::Array(array![v, i])),
+            "last-where" => array
+                .last_where(vm, args.expect("function")?)?
+                .map_or(Value::None, |(v, i)| Value::Array(array![v, i])),
             "filter" => Value::Array(array.filter(vm, args.expect("function")?)?),
             "map" => Value::Array(array.map(vm, args.expect("function")?)?),
             "fold" => {
@@ -107,15 +190,32 @@ pub fn call(
             }
             "any" => Value::Bool(array.any(vm, args.expect("function")?)?),
             "all" => Value::Bool(array.all(vm, args.expect("function")?)?),
-            "flatten" => Value::Array(array.flatten()),
+            "flatten" => Value::Array(array.flatten(args.named("depth")?)),
+            "zip" => Value::Array(array.zip(&args.expect("other")?)),
+            "enumerate" => {
+                Value::Array(array.enumerate(args.named("start")?.unwrap_or(0)).at(span)?)
+            }
             "rev" => Value::Array(array.rev()),
+            "unzip" => Value::Array(array.unzip().at(span)?),
             "split" => Value::Array(array.split(args.expect("separator")?)),
+            "chunks" => Value::Array(array.chunks(args.expect("chunk size")?)),
+            "windows" => Value::Array(array.windows(args.expect("window size")?)),
             "join" => {
                 let sep = args.eat()?;
                 let last = args.named("last")?;
                 array.join(sep, last).at(span)?
             }
             "sorted" => Value::Array(array.sorted().at(span)?),
+            "unique" => Value::Array(array.unique(vm, args.named("key")?)?),
+            "sum" => array.sum(vm, span, args.named("key")?)?,
+            "product" => array.product(vm, span, args.named("key")?)?,
+            "running-max" => Value::Array(array.running(Ordering::Greater).at(span)?),
+            "running-min" => Value::Array(array.running(Ordering::Less).at(span)?),
+            "argsort" => Value::Array(array.argsort(vm, span, args.named("key")?)?),
+            "reorder" => Value::Array(array.reorder(&args.expect("indices")?).at(span)?),
+            "common-prefix" => Value::Str(array.common_affix(false).at(span)?),
+            "common-suffix" => Value::Str(array.common_affix(true).at(span)?),
+            "equal-ignoring" => equal_ignoring(Value::Array(array), &mut args)?,
             _ => return missing(),
         },
 
@@ -125,11 +225,21 @@ pub fn call(
             "keys" => Value::Array(dict.keys()),
             "values" => Value::Array(dict.values()),
             "pairs" => Value::Array(dict.pairs()),
+            "equal-ignoring" => equal_ignoring(Value::Dict(dict), &mut args)?,
+            "is-subset" => Value::Bool(dict.is_subset(&args.expect("other")?)),
+            "is-superset" => Value::Bool(args.expect::<Dict>("other")?.is_subset(&dict)),
+            "merge" => {
+                let mut dict = dict;
+                let strategy = args.named("array-strategy")?.unwrap_or_default();
+                dict.merge(args.expect("other")?, strategy);
+                Value::Dict(dict)
+            }
             _ => return missing(),
         },
 
         Value::Func(func) => match method {
             "with" => Value::Func(func.with(args.take())),
+            "complement" => Value::Func(func.complement()),
             "where" => {
                 let fields = args.to_named();
                 args.items.retain(|arg| arg.name.is_none());
@@ -249,64 +359,137 @@ fn missing_method(type_name: &str, method: &str) -> String {
     format!("type {type_name} has no method `{method}`")
 }
 
+/// Compare a collection with another value, disregarding the values at the
+/// paths given in the arguments.
+fn equal_ignoring(value: Value, args: &mut Args) -> SourceResult<Value> {
+    let other: Value = args.expect("other")?;
+    let paths: Vec<Array> = args.expect("paths")?;
+    let paths: Vec<&[Value]> = paths.iter().map(Array::as_slice).collect();
+    Ok(Value::Bool(ops::equal_ignoring(&value, &other, &paths)))
+}
+
 /// List the available methods for a type and whether they take arguments.
 pub fn methods_on(type_name: &str) -> &[(&'static str, bool)] {
     match type_name {
-        "color" => &[("lighten", true), ("darken", true), ("negate", false)],
+        "color" => &[
+            ("lighten", true),
+            ("darken", true),
+            ("negate", false),
+            ("with-alpha", true),
+            ("fade", true),
+            ("premultiply", false),
+            ("unpremultiply", false),
+            ("hue", false),
+            ("saturation", false),
+            ("lightness", false),
+            ("to-hex", false),
+            ("to-rgb-string", false),
+        ],
+        "angle" => &[("normalize", false), ("to-deg", false), ("to-rad", false)],
+        "length" => &[
+            ("abs-part", false),
+            ("convert", true),
+            ("rel-part", false),
+            ("resolve", true),
+            ("to-cm", false),
+            ("to-in", false),
+            ("to-mm", false),
+            ("to-pt", false),
+        ],
+        "ratio" | "relative length" => {
+            &[("abs-part", false), ("rel-part", false), ("resolve", true)]
+        }
         "string" => &[
             ("len", false),
             ("at", true),
             ("clusters", false),
             ("codepoints", false),
             ("contains", true),
+            ("count-graphemes", false),
             ("ends-with", true),
+            ("expand-tabs", true),
+            ("is-alpha", false),
+            ("is-alphanumeric", false),
+            ("is-numeric", false),
             ("find", true),
             ("first", false),
             ("last", false),
+            ("mask", true),
             ("match", true),
             ("matches", true),
             ("position", true),
             ("replace", true),
+            ("replace-map", true),
+            ("rev", false),
             ("slice", true),
             ("split", true),
             ("starts-with", true),
             ("trim", true),
         ],
-        "content" => &[("func", false), ("has", true), ("at", true), ("location", false)],
+        "content" => &[
+            ("func", false),
+            ("has", true),
+            ("at", true),
+            ("count", true),
+            ("location", false),
+        ],
         "array" => &[
             ("all", true),
             ("any", true),
+            ("argsort", true),
             ("at", true),
+            ("chunks", true),
+            ("common-prefix", false),
+            ("common-suffix", false),
             ("contains", true),
+            ("enumerate", true),
+            ("equal-ignoring", true),
             ("filter", true),
             ("find", true),
             ("first", false),
-            ("flatten", false),
+            ("first-where", true),
+            ("flatten", true),
             ("fold", true),
             ("insert", true),
+            ("is-subset", true),
+            ("is-superset", true),
             ("split", true),
             ("join", true),
             ("last", false),
+            ("last-where", true),
             ("len", false),
             ("map", true),
             ("pop", false),
             ("position", true),
+            ("product", true),
             ("push", true),
             ("remove", true),
+            ("reorder", true),
             ("rev", false),
+            ("running-max", false),
+            ("running-min", false),
             ("slice", true),
             ("sorted", false),
+            ("sum", true),
+            ("unique", true),
+            ("unzip", false),
+            ("windows", true),
+            ("zip", true),
         ],
         "dictionary" => &[
             ("at", true),
+            ("equal-ignoring", true),
             ("insert", true),
+            ("is-subset", true),
+            ("is-superset", true),
             ("keys", false),
             ("len", false),
+            ("merge", true),
             ("pairs", false),
             ("remove", true),
             ("values", false),
         ],
-        "function" => &[("where", true), ("with", true)],
+        "function" => &[("complement", false), ("where", true), ("with", true)],
         "arguments" => &[("named", false), ("pos", false)],
         "location" => &[("page", false), ("position", false)],
         "counter" => &[
@@ -35,6 +35,8 @@ pub use self::str::*;
 pub use self::symbol::*;
 pub use self::value::*;
 
+pub use self::ops::coalesce;
+
 pub(crate) use self::methods::methods_on;
 
 use std::collections::BTreeMap;
@@ -4,9 +4,10 @@ use std::cmp::Ordering;
 
 use ecow::eco_format;
 
-use super::{format_str, Regex, Value};
+use super::{format_str, Regex, Type, Value, ValueKind};
 use crate::diag::StrResult;
 use crate::geom::{Axes, Axis, GenAlign, Length, Numeric, PartialStroke, Rel, Smart};
+use crate::syntax::ast;
 use Value::*;
 
 /// Bail with a type mismatch error.
@@ -53,7 +54,7 @@ pub fn pos(value: Value) -> StrResult<Value> {
 /// Compute the negation of a value.
 pub fn neg(value: Value) -> StrResult<Value> {
     Ok(match value {
-        Int(v) => Int(-v),
+        Int(v) => v.checked_neg().map_or(Float(-(v as f64)), Int),
         Float(v) => Float(-v),
         Length(v) => Length(-v),
         Angle(v) => Angle(-v),
@@ -70,7 +71,7 @@ pub fn add(lhs: Value, rhs: Value) -> StrResult<Value> {
         (a, None) => a,
         (None, b) => b,
 
-        (Int(a), Int(b)) => Int(a + b),
+        (Int(a), Int(b)) => a.checked_add(b).map_or(Float(a as f64 + b as f64), Int),
         (Int(a), Float(b)) => Float(a as f64 + b),
         (Float(a), Int(b)) => Float(a + b as f64),
         (Float(a), Float(b)) => Float(a + b),
@@ -126,9 +127,28 @@ pub fn add(lhs: Value, rhs: Value) -> StrResult<Value> {
                 }));
             };
 
+            // Either side may know how to add the other one.
+            if let Some(sum) = a.dyn_add(&Dyn(b.clone())) {
+                return Ok(sum);
+            }
+
+            if let Some(sum) = b.dyn_add(&Dyn(a.clone())) {
+                return Ok(sum);
+            }
+
             mismatch!("cannot add {} and {}", a, b);
         }
 
+        (Dyn(a), b) => match a.dyn_add(&b) {
+            Some(sum) => sum,
+            Option::None => mismatch!("cannot add {} and {}", a, b),
+        },
+
+        (a, Dyn(b)) => match b.dyn_add(&a) {
+            Some(sum) => sum,
+            Option::None => mismatch!("cannot add {} and {}", a, b),
+        },
+
         (a, b) => mismatch!("cannot add {} and {}", a, b),
     })
 }
@@ -136,7 +156,7 @@ pub fn add(lhs: Value, rhs: Value) -> StrResult<Value> {
 /// Compute the difference of two values.
 pub fn sub(lhs: Value, rhs: Value) -> StrResult<Value> {
     Ok(match (lhs, rhs) {
-        (Int(a), Int(b)) => Int(a - b),
+        (Int(a), Int(b)) => a.checked_sub(b).map_or(Float(a as f64 - b as f64), Int),
         (Int(a), Float(b)) => Float(a as f64 - b),
         (Float(a), Int(b)) => Float(a - b as f64),
         (Float(a), Float(b)) => Float(a - b),
@@ -164,7 +184,7 @@ pub fn sub(lhs: Value, rhs: Value) -> StrResult<Value> {
 /// Compute the product of two values.
 pub fn mul(lhs: Value, rhs: Value) -> StrResult<Value> {
     Ok(match (lhs, rhs) {
-        (Int(a), Int(b)) => Int(a * b),
+        (Int(a), Int(b)) => a.checked_mul(b).map_or(Float(a as f64 * b as f64), Int),
         (Int(a), Float(b)) => Float(a as f64 * b),
         (Float(a), Int(b)) => Float(a * b as f64),
         (Float(a), Float(b)) => Float(a * b),
@@ -179,6 +199,7 @@ pub fn mul(lhs: Value, rhs: Value) -> StrResult<Value> {
         (Int(a), Angle(b)) => Angle(a as f64 * b),
         (Float(a), Angle(b)) => Angle(a * b),
 
+        (Ratio(a), Ratio(b)) => Ratio(a * b),
         (Ratio(a), Int(b)) => Ratio(a * b as f64),
         (Ratio(a), Float(b)) => Ratio(a * b),
         (Float(a), Ratio(b)) => Ratio(a * b),
@@ -294,6 +315,15 @@ pub fn or(lhs: Value, rhs: Value) -> StrResult<Value> {
     }
 }
 
+/// Return the value unless it is `none`, in which case the fallback is
+/// computed and returned instead.
+pub fn coalesce(value: Value, fallback: impl FnOnce() -> Value) -> Value {
+    match value {
+        Value::None => fallback(),
+        v => v,
+    }
+}
+
 /// Compute whether two values are equal.
 pub fn eq(lhs: Value, rhs: Value) -> StrResult<Value> {
     Ok(Bool(equal(&lhs, &rhs)))
@@ -346,7 +376,7 @@ pub fn equal(lhs: &Value, rhs: &Value) -> bool {
         (Func(a), Func(b)) => a == b,
         (Args(a), Args(b)) => a == b,
         (Module(a), Module(b)) => a == b,
-        (Dyn(a), Dyn(b)) => a == b,
+        (Dyn(a), Dyn(b)) if a == b => true,
 
         // Some technically different things should compare equal.
         (&Int(a), &Float(b)) => a as f64 == b,
@@ -356,10 +386,52 @@ pub fn equal(lhs: &Value, rhs: &Value) -> bool {
         (&Relative(a), &Length(b)) => a.abs == b && a.rel.is_zero(),
         (&Relative(a), &Ratio(b)) => a.rel == b && a.abs.is_zero(),
 
+        // Dynamic values are equal to whatever they compare equal with.
+        (Dyn(_), _) | (_, Dyn(_)) => compare(lhs, rhs) == Some(Ordering::Equal),
+
         _ => false,
     }
 }
 
+/// Determine whether two values are equal, disregarding the values at the
+/// given paths.
+///
+/// A path is a sequence of dictionary keys and (possibly negative) array
+/// indices leading from the compared values to a nested value.
+pub fn equal_ignoring(lhs: &Value, rhs: &Value, ignored: &[&[Value]]) -> bool {
+    if ignored.iter().any(|path| path.is_empty()) {
+        return true;
+    }
+
+    // The remaining paths below the given dictionary key or array index.
+    let below = |matches: &dyn Fn(&Value) -> bool| -> Vec<&[Value]> {
+        ignored
+            .iter()
+            .filter(|path| matches(&path[0]))
+            .map(|path| &path[1..])
+            .collect()
+    };
+
+    match (lhs, rhs) {
+        (Dict(a), Dict(b)) => a.iter().chain(b.iter()).all(|(key, _)| {
+            let ignored = below(&|v| matches!(v, Str(k) if k == key));
+            match (a.at(key), b.at(key)) {
+                (Ok(x), Ok(y)) => equal_ignoring(x, y, &ignored),
+                _ => ignored.iter().any(|path| path.is_empty()),
+            }
+        }),
+        (Array(a), Array(b)) if a.len() == b.len() => {
+            let len = a.len();
+            a.iter().zip(b.iter()).enumerate().all(|(i, (x, y))| {
+                let i = i as i64;
+                let ignored = below(&|v| matches!(v, &Int(k) if k == i || k == i - len));
+                equal_ignoring(x, y, &ignored)
+            })
+        }
+        _ => equal(lhs, rhs),
+    }
+}
+
 /// Compare two values.
 pub fn compare(lhs: &Value, rhs: &Value) -> Option<Ordering> {
     match (lhs, rhs) {
@@ -381,6 +453,13 @@ pub fn compare(lhs: &Value, rhs: &Value) -> Option<Ordering> {
         (&Relative(a), &Length(b)) if a.rel.is_zero() => a.abs.partial_cmp(&b),
         (&Relative(a), &Ratio(b)) if a.abs.is_zero() => a.rel.partial_cmp(&b),
 
+        // Dynamic types decide themselves what they are comparable with.
+        (Dyn(a), Dyn(b)) => a
+            .dyn_partial_cmp(rhs)
+            .or_else(|| b.dyn_partial_cmp(lhs).map(Ordering::reverse)),
+        (Dyn(a), _) => a.dyn_partial_cmp(rhs),
+        (_, Dyn(b)) => b.dyn_partial_cmp(lhs).map(Ordering::reverse),
+
         _ => Option::None,
     }
 }
@@ -413,3 +492,295 @@ pub fn contains(lhs: &Value, rhs: &Value) -> Option<bool> {
         _ => Option::None,
     }
 }
+
+/// Determine the kind of value that a binary operator produces for operands
+/// of the given kinds.
+///
+/// Returns `None` if the operator can't be applied to these kinds. Operations
+/// that can fail for some values, like dividing by a relative length, are
+/// assumed to succeed, and integer overflow is not taken into account.
+/// Operations that dynamic types define themselves, except for alignments,
+/// strokes and regular expressions, are not known and yield `None`.
+pub fn result_type(op: ast::BinOp, lhs: ValueKind, rhs: ValueKind) -> Option<ValueKind> {
+    use ast::BinOp::*;
+    use ValueKind as K;
+
+    let num = |k| matches!(k, K::Int | K::Float);
+    let rel = |k| matches!(k, K::Length | K::Ratio | K::Relative);
+    let text = |k| matches!(k, K::Symbol | K::Str);
+
+    // Lengths and ratios can only be divided by and compared with each other
+    // through relative lengths.
+    let commensurable =
+        |a, b| rel(a) && rel(b) && (a == b || a == K::Relative || b == K::Relative);
+
+    Some(match op {
+        Add | AddAssign => match (lhs, rhs) {
+            (a, K::None) => a,
+            (K::None, b) => b,
+            (K::Int, K::Int) => K::Int,
+            (a, b) if num(a) && num(b) => K::Float,
+            (K::Angle, K::Angle) => K::Angle,
+            (K::Length, K::Length) => K::Length,
+            (K::Ratio, K::Ratio) => K::Ratio,
+            (a, b) if rel(a) && rel(b) => K::Relative,
+            (K::Fraction, K::Fraction) => K::Fraction,
+            (a, b) if text(a) && text(b) => K::Str,
+            (K::Content, b) if b == K::Content || text(b) => K::Content,
+            (a, K::Content) if text(a) => K::Content,
+            (K::Array, K::Array) => K::Array,
+            (K::Dict, K::Dict) => K::Dict,
+            (K::Color, K::Length) | (K::Length, K::Color) => {
+                K::Dyn(PartialStroke::TYPE_NAME)
+            }
+            (K::Dyn(a), K::Dyn(b))
+                if a == GenAlign::TYPE_NAME && b == GenAlign::TYPE_NAME =>
+            {
+                K::Dyn(Axes::<GenAlign>::TYPE_NAME)
+            }
+            _ => return Option::None,
+        },
+        Sub | SubAssign => match (lhs, rhs) {
+            (K::Int, K::Int) => K::Int,
+            (a, b) if num(a) && num(b) => K::Float,
+            (K::Angle, K::Angle) => K::Angle,
+            (K::Length, K::Length) => K::Length,
+            (K::Ratio, K::Ratio) => K::Ratio,
+            (a, b) if rel(a) && rel(b) => K::Relative,
+            (K::Fraction, K::Fraction) => K::Fraction,
+            _ => return Option::None,
+        },
+        Mul | MulAssign => match (lhs, rhs) {
+            (K::Int, K::Int) => K::Int,
+            (a, b) if num(a) && num(b) => K::Float,
+            (K::Ratio, K::Ratio) => K::Ratio,
+            (a, b) | (b, a) if num(b) => match a {
+                K::Length | K::Angle | K::Ratio | K::Relative | K::Fraction => a,
+                K::Str | K::Array | K::Content if b == K::Int => a,
+                _ => return Option::None,
+            },
+            _ => return Option::None,
+        },
+        Div | DivAssign => match (lhs, rhs) {
+            (a, b) if num(a) && num(b) => K::Float,
+            (a, b) if num(b) => match a {
+                K::Length | K::Angle | K::Ratio | K::Relative | K::Fraction => a,
+                _ => return Option::None,
+            },
+            (a, b) if commensurable(a, b) => K::Float,
+            (K::Angle, K::Angle) | (K::Fraction, K::Fraction) => K::Float,
+            _ => return Option::None,
+        },
+        And | Or => match (lhs, rhs) {
+            (K::Bool, K::Bool) => K::Bool,
+            _ => return Option::None,
+        },
+        Eq | Neq => K::Bool,
+        Lt | Leq | Gt | Geq => match (lhs, rhs) {
+            (a, b) if num(a) && num(b) => K::Bool,
+            (a, b) if commensurable(a, b) => K::Bool,
+            (a, b)
+                if a == b && matches!(a, K::Bool | K::Angle | K::Fraction | K::Str) =>
+            {
+                K::Bool
+            }
+            _ => return Option::None,
+        },
+        In | NotIn => match (lhs, rhs) {
+            (K::Str, K::Str) | (K::Str, K::Dict) | (_, K::Array) => K::Bool,
+            (K::Dyn(a), K::Str) if a == Regex::TYPE_NAME => K::Bool,
+            _ => return Option::None,
+        },
+        Assign => rhs,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::eval::{array, Type};
+    use crate::geom;
+
+    /// An amount of money in cents.
+    #[derive(Debug, Copy, Clone, PartialEq, Hash)]
+    struct Money(i64);
+
+    impl Type for Money {
+        const TYPE_NAME: &'static str = "money";
+
+        fn dyn_add(&self, other: &Value) -> Option<Value> {
+            let cents = match other {
+                Dyn(other) => other.downcast::<Money>()?.0,
+                Int(other) => *other,
+                _ => return Option::None,
+            };
+            Some(Value::dynamic(Money(self.0 + cents)))
+        }
+
+        fn dyn_partial_cmp(&self, other: &Value) -> Option<Ordering> {
+            match other {
+                Dyn(other) => self.0.partial_cmp(&other.downcast::<Money>()?.0),
+                Int(other) => self.0.partial_cmp(other),
+                _ => Option::None,
+            }
+        }
+    }
+
+    #[test]
+    fn test_result_type() {
+        use ast::BinOp::*;
+        use ValueKind as K;
+        assert_eq!(result_type(Add, K::Int, K::Int), Some(K::Int));
+        assert_eq!(result_type(Add, K::Int, K::Float), Some(K::Float));
+        assert_eq!(result_type(Div, K::Int, K::Int), Some(K::Float));
+        assert_eq!(result_type(Add, K::Length, K::Ratio), Some(K::Relative));
+        assert_eq!(result_type(Div, K::Length, K::Length), Some(K::Float));
+        assert_eq!(result_type(Div, K::Length, K::Relative), Some(K::Float));
+        assert_eq!(result_type(Mul, K::Str, K::Int), Some(K::Str));
+        assert_eq!(result_type(Mul, K::Int, K::Content), Some(K::Content));
+        assert_eq!(result_type(Add, K::Content, K::Content), Some(K::Content));
+        assert_eq!(result_type(Add, K::Str, K::Content), Some(K::Content));
+        assert_eq!(result_type(Add, K::Color, K::Length), Some(K::Dyn("stroke")));
+        assert_eq!(
+            result_type(Add, K::Dyn("alignment"), K::Dyn("alignment")),
+            Some(K::Dyn("2d alignment"))
+        );
+        assert_eq!(result_type(Lt, K::Int, K::Float), Some(K::Bool));
+        assert_eq!(result_type(In, K::Str, K::Dict), Some(K::Bool));
+        assert_eq!(result_type(In, K::Dyn("regular expression"), K::Str), Some(K::Bool));
+        assert_eq!(result_type(AddAssign, K::Array, K::Array), Some(K::Array));
+        assert_eq!(result_type(Add, K::Color, K::Int), Option::None);
+        assert_eq!(result_type(Mul, K::Str, K::Float), Option::None);
+        assert_eq!(result_type(Div, K::Length, K::Ratio), Option::None);
+        assert_eq!(result_type(Lt, K::Str, K::Int), Option::None);
+        assert_eq!(result_type(Add, K::Int, K::Content), Option::None);
+        assert_eq!(Value::arith_result_type(Add, K::Int, K::Float), Some(K::Float));
+    }
+
+    #[test]
+    fn test_result_type_matches_operators() {
+        use ast::BinOp;
+
+        let pt = Length::from(geom::Abs::pt(1.0));
+        let samples = [
+            Value::None,
+            Value::Auto,
+            Bool(true),
+            Int(2),
+            Float(1.5),
+            Length(pt),
+            Angle(geom::Angle::deg(1.0)),
+            Ratio(geom::Ratio::one()),
+            Relative(geom::Ratio::one() + pt),
+            Relative(geom::Rel::new(geom::Ratio::zero(), pt)),
+            Relative(geom::Rel::new(geom::Ratio::one(), Length::zero())),
+            Fraction(geom::Fr::one()),
+            Color(geom::Color::BLACK),
+            Str("a".into()),
+            Array(array![1]),
+            Dict(crate::eval::dict!["a" => 1]),
+        ];
+
+        let ops: [(BinOp, fn(Value, Value) -> StrResult<Value>); 19] = [
+            (BinOp::Add, add),
+            (BinOp::Sub, sub),
+            (BinOp::Mul, mul),
+            (BinOp::Div, div),
+            (BinOp::And, and),
+            (BinOp::Or, or),
+            (BinOp::Eq, eq),
+            (BinOp::Neq, neq),
+            (BinOp::Lt, lt),
+            (BinOp::Leq, leq),
+            (BinOp::Gt, gt),
+            (BinOp::Geq, geq),
+            (BinOp::Assign, |_, rhs| Ok(rhs)),
+            (BinOp::In, in_),
+            (BinOp::NotIn, not_in),
+            (BinOp::AddAssign, add),
+            (BinOp::SubAssign, sub),
+            (BinOp::MulAssign, mul),
+            (BinOp::DivAssign, div),
+        ];
+
+        // Some operations only succeed for certain values, like comparing a
+        // length with a relative length without a ratio part. There are
+        // multiple samples of such kinds, and the table must match the
+        // operator if it succeeds for any of them.
+        for (op, apply) in ops {
+            for a in &samples {
+                for b in &samples {
+                    let found = result_type(op, a.kind(), b.kind());
+                    let mut expected = Option::None;
+                    for x in samples.iter().filter(|x| x.kind() == a.kind()) {
+                        for y in samples.iter().filter(|y| y.kind() == b.kind()) {
+                            if let Ok(v) = apply(x.clone(), y.clone()) {
+                                assert_eq!(
+                                    found,
+                                    Some(v.kind()),
+                                    "{op:?} on {x:?} and {y:?}"
+                                );
+                                expected = found;
+                            }
+                        }
+                    }
+                    assert_eq!(found, expected, "{op:?} on {a:?} and {b:?}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_dynamic_operators() {
+        let money = |cents| Value::dynamic(Money(cents));
+        assert_eq!(add(money(150), money(250)), Ok(money(400)));
+        assert_eq!(add(money(150), Int(250)), Ok(money(400)));
+        assert_eq!(add(Int(250), money(150)), Ok(money(400)));
+        assert_eq!(add(money(150), Float(2.5)), Err("cannot add money and float".into()));
+        assert_eq!(add(Float(2.5), money(150)), Err("cannot add float and money".into()));
+        assert_eq!(lt(money(150), money(250)), Ok(Bool(true)));
+        assert_eq!(gt(money(150), Int(100)), Ok(Bool(true)));
+        assert_eq!(gt(Int(100), money(150)), Ok(Bool(false)));
+        assert!(lt(money(150), Str("a".into())).is_err());
+
+        // Values that compare as equal must also be equal.
+        assert_eq!(leq(money(150), Int(150)), Ok(Bool(true)));
+        assert_eq!(geq(Int(150), money(150)), Ok(Bool(true)));
+        assert_eq!(eq(money(150), Int(150)), Ok(Bool(true)));
+        assert_eq!(eq(Int(150), money(150)), Ok(Bool(true)));
+        assert_eq!(neq(money(150), Int(151)), Ok(Bool(true)));
+        assert_eq!(eq(money(150), Str("150".into())), Ok(Bool(false)));
+        for (a, b) in [(money(150), Int(150)), (money(1), Int(2)), (Int(3), money(3))] {
+            let same = compare(&a, &b) == Some(Ordering::Equal);
+            assert_eq!(equal(&a, &b), same);
+            assert_eq!(equal(&b, &a), same);
+        }
+    }
+
+    #[test]
+    fn test_int_overflow() {
+        assert_eq!(add(Int(1), Int(2)), Ok(Int(3)));
+        assert_eq!(mul(Int(i64::MAX), Int(2)), Ok(Float(i64::MAX as f64 * 2.0)));
+        assert_eq!(add(Int(i64::MAX), Int(1)), Ok(Float(i64::MAX as f64 + 1.0)));
+        assert_eq!(sub(Int(i64::MIN), Int(1)), Ok(Float(i64::MIN as f64 - 1.0)));
+        assert_eq!(neg(Int(i64::MIN)), Ok(Float(-(i64::MIN as f64))));
+        assert!(matches!(mul(Int(i64::MAX), Int(2)), Ok(Float(v)) if v.is_finite()));
+    }
+
+    #[test]
+    fn test_equal() {
+        assert!(equal(&Int(3), &Int(3)));
+        assert!(equal(&Int(3), &Float(3.0)));
+        assert!(!equal(&Float(f64::NAN), &Float(f64::NAN)));
+        assert!(!equal(&Int(3), &Str("3".into())));
+        assert_ne!(Array(array![f64::NAN]), Array(array![f64::NAN]));
+    }
+
+    #[test]
+    fn test_coalesce() {
+        assert_eq!(coalesce(Value::None, || Int(5)), Int(5));
+        assert_eq!(coalesce(Int(0), || Int(5)), Int(0));
+        assert_eq!(coalesce(Value::Auto, || Int(5)), Value::Auto);
+        assert_eq!(coalesce(Bool(false), || unreachable!()), Bool(false));
+    }
+}
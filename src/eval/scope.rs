@@ -169,3 +169,24 @@ impl Slot {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scopes_enter_exit() {
+        let mut scopes = Scopes::new(None);
+        scopes.top.define("outer", 1);
+
+        scopes.enter();
+        scopes.top.define("inner", 2);
+        scopes.top.define("outer", 3);
+        assert_eq!(scopes.get("inner"), Ok(&Value::Int(2)));
+        assert_eq!(scopes.get("outer"), Ok(&Value::Int(3)));
+
+        scopes.exit();
+        assert_eq!(scopes.get("inner"), Err("unknown variable".into()));
+        assert_eq!(scopes.get("outer"), Ok(&Value::Int(1)));
+    }
+}
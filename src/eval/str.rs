@@ -1,6 +1,7 @@
 use std::borrow::{Borrow, Cow};
 use std::fmt::{self, Debug, Display, Formatter, Write};
 use std::hash::{Hash, Hasher};
+use std::num::NonZeroUsize;
 use std::ops::{Add, AddAssign, Deref};
 
 use ecow::EcoString;
@@ -84,6 +85,22 @@ impl Str {
         self.as_str().graphemes(true).map(|s| Value::Str(s.into())).collect()
     }
 
+    /// Reverse the order of the grapheme clusters in the string.
+    pub fn rev(&self) -> Self {
+        self.as_str().graphemes(true).rev().collect::<String>().into()
+    }
+
+    /// The number of grapheme clusters in the string.
+    pub fn count_graphemes(&self) -> i64 {
+        self.as_str().graphemes(true).count() as i64
+    }
+
+    /// Whether the string is non-empty and all its characters satisfy the
+    /// predicate.
+    pub fn all_chars(&self, f: impl FnMut(char) -> bool) -> bool {
+        !self.0.is_empty() && self.0.chars().all(f)
+    }
+
     /// The codepoints the string consists of.
     pub fn codepoints(&self) -> Array {
         self.chars().map(|c| Value::Str(c.into())).collect()
@@ -266,6 +283,90 @@ impl Str {
         }
     }
 
+    /// Replace occurrences of the keys of a mapping with their values in a
+    /// single left-to-right pass.
+    ///
+    /// When multiple keys match at the same position, the longest one wins.
+    /// Replacements are not searched again. Empty keys never match.
+    pub fn replace_map(&self, mapping: &Dict) -> StrResult<Self> {
+        let mut pairs = Vec::with_capacity(mapping.len() as usize);
+        for (key, value) in mapping {
+            let Value::Str(with) = value else {
+                return Err(eco_format!("expected string, found {}", value.type_name()));
+            };
+            if !key.is_empty() {
+                pairs.push((key.as_str(), with.as_str()));
+            }
+        }
+
+        // Try longer keys first so that the longest match wins.
+        pairs.sort_by_key(|(key, _)| std::cmp::Reverse(key.len()));
+
+        let mut replaced = EcoString::new();
+        let mut rest = self.as_str();
+        while let Some(c) = rest.chars().next() {
+            match pairs.iter().find(|(key, _)| rest.starts_with(key)) {
+                Some((key, with)) => {
+                    replaced.push_str(with);
+                    rest = &rest[key.len()..];
+                }
+                None => {
+                    replaced.push(c);
+                    rest = &rest[c.len_utf8()..];
+                }
+            }
+        }
+
+        Ok(Self(replaced))
+    }
+
+    /// Replace all but the first and last few characters with a mask.
+    ///
+    /// Returns the string unchanged if there is nothing left to mask.
+    pub fn mask(&self, keep_start: usize, keep_end: usize, with: &str) -> Self {
+        let count = self.0.chars().count();
+        if keep_start.saturating_add(keep_end) >= count {
+            return self.clone();
+        }
+
+        let mut masked = EcoString::new();
+        for (i, c) in self.0.chars().enumerate() {
+            if i < keep_start || i >= count - keep_end {
+                masked.push(c);
+            } else {
+                masked.push_str(with);
+            }
+        }
+        Self(masked)
+    }
+
+    /// Replace tabs with spaces up to the next multiple of `width` columns.
+    ///
+    /// Columns are counted in characters and restart after each line break.
+    pub fn expand_tabs(&self, width: NonZeroUsize) -> Self {
+        let width = width.get();
+        let mut expanded = EcoString::new();
+        let mut column = 0;
+        for c in self.0.chars() {
+            match c {
+                '\t' => {
+                    let spaces = width - column % width;
+                    expanded.push_str(&" ".repeat(spaces));
+                    column += spaces;
+                }
+                '\n' | '\r' => {
+                    expanded.push(c);
+                    column = 0;
+                }
+                _ => {
+                    expanded.push(c);
+                    column += 1;
+                }
+            }
+        }
+        Self(expanded)
+    }
+
     /// Repeat the string a number of times.
     pub fn repeat(&self, n: i64) -> StrResult<Self> {
         let n = usize::try_from(n)
@@ -1,6 +1,7 @@
 use std::any::Any;
 use std::cmp::Ordering;
-use std::fmt::{self, Debug, Formatter};
+use std::collections::HashSet;
+use std::fmt::{self, Debug, Display, Formatter};
 use std::hash::{Hash, Hasher};
 use std::sync::Arc;
 
@@ -12,7 +13,7 @@ use super::{
     Label, Module, Str, Symbol,
 };
 use crate::diag::StrResult;
-use crate::geom::{Abs, Angle, Color, Em, Fr, Length, Ratio, Rel};
+use crate::geom::{Abs, Angle, Color, Em, Fr, Length, Numeric, Ratio, Rel};
 use crate::model::Styles;
 use crate::syntax::{ast, Span};
 
@@ -89,32 +90,51 @@ impl Value {
 
     /// The name of the stored value's type.
     pub fn type_name(&self) -> &'static str {
+        self.kind().name()
+    }
+
+    /// The kind of the stored value.
+    pub fn kind(&self) -> ValueKind {
         match self {
-            Self::None => "none",
-            Self::Auto => "auto",
-            Self::Bool(_) => bool::TYPE_NAME,
-            Self::Int(_) => i64::TYPE_NAME,
-            Self::Float(_) => f64::TYPE_NAME,
-            Self::Length(_) => Length::TYPE_NAME,
-            Self::Angle(_) => Angle::TYPE_NAME,
-            Self::Ratio(_) => Ratio::TYPE_NAME,
-            Self::Relative(_) => Rel::<Length>::TYPE_NAME,
-            Self::Fraction(_) => Fr::TYPE_NAME,
-            Self::Color(_) => Color::TYPE_NAME,
-            Self::Symbol(_) => Symbol::TYPE_NAME,
-            Self::Str(_) => Str::TYPE_NAME,
-            Self::Label(_) => Label::TYPE_NAME,
-            Self::Content(_) => Content::TYPE_NAME,
-            Self::Styles(_) => Styles::TYPE_NAME,
-            Self::Array(_) => Array::TYPE_NAME,
-            Self::Dict(_) => Dict::TYPE_NAME,
-            Self::Func(_) => Func::TYPE_NAME,
-            Self::Args(_) => Args::TYPE_NAME,
-            Self::Module(_) => Module::TYPE_NAME,
-            Self::Dyn(v) => v.type_name(),
+            Self::None => ValueKind::None,
+            Self::Auto => ValueKind::Auto,
+            Self::Bool(_) => ValueKind::Bool,
+            Self::Int(_) => ValueKind::Int,
+            Self::Float(_) => ValueKind::Float,
+            Self::Length(_) => ValueKind::Length,
+            Self::Angle(_) => ValueKind::Angle,
+            Self::Ratio(_) => ValueKind::Ratio,
+            Self::Relative(_) => ValueKind::Relative,
+            Self::Fraction(_) => ValueKind::Fraction,
+            Self::Color(_) => ValueKind::Color,
+            Self::Symbol(_) => ValueKind::Symbol,
+            Self::Str(_) => ValueKind::Str,
+            Self::Label(_) => ValueKind::Label,
+            Self::Content(_) => ValueKind::Content,
+            Self::Styles(_) => ValueKind::Styles,
+            Self::Array(_) => ValueKind::Array,
+            Self::Dict(_) => ValueKind::Dict,
+            Self::Func(_) => ValueKind::Func,
+            Self::Args(_) => ValueKind::Args,
+            Self::Module(_) => ValueKind::Module,
+            Self::Dyn(v) => ValueKind::Dyn(v.type_name()),
         }
     }
 
+    /// The kind of value a binary operator produces for operands of the given
+    /// kinds, or `None` if the operator can't be applied to them.
+    ///
+    /// This only depends on the kinds, not on the values: Operations that can
+    /// fail for some values, like dividing by a relative length, are assumed
+    /// to succeed, and integer overflow is not taken into account.
+    pub fn arith_result_type(
+        op: ast::BinOp,
+        lhs: ValueKind,
+        rhs: ValueKind,
+    ) -> Option<ValueKind> {
+        ops::result_type(op, lhs, rhs)
+    }
+
     /// Try to cast the value into a specific type.
     pub fn cast<T: Cast>(self) -> StrResult<T> {
         T::cast(self)
@@ -131,6 +151,119 @@ impl Value {
         }
     }
 
+    /// Borrow the value the given `key` maps to if this is a dictionary.
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        match self {
+            Self::Dict(dict) => dict.at(key).ok(),
+            _ => Option::None,
+        }
+    }
+
+    /// Whether this is a dictionary that contains the given `key`.
+    pub fn contains_key(&self, key: &str) -> bool {
+        matches!(self, Self::Dict(dict) if dict.contains(key))
+    }
+
+    /// Extract the item of an array or the grapheme cluster of a string at the
+    /// given index.
+    ///
+    /// Negative indices count from the back. Returns `None` for out of bounds
+    /// indices and for values that are neither arrays nor strings.
+    pub fn at(&self, index: i64) -> Option<Value> {
+        match self {
+            Self::Array(array) => array.at(index).ok().cloned(),
+            Self::Str(string) => string.at(index).ok().map(Self::Str),
+            _ => Option::None,
+        }
+    }
+
+    /// Whether this is an empty collection.
+    ///
+    /// Content is only empty if it is a sequence without children, so content
+    /// with a single empty text element is not. Returns `None` for values that are
+    /// not arrays, dictionaries, strings or content.
+    pub fn is_empty(&self) -> Option<bool> {
+        match self {
+            Self::Array(array) => Some(array.len() == 0),
+            Self::Dict(dict) => Some(dict.is_empty()),
+            Self::Str(string) => Some(string.is_empty()),
+            Self::Content(content) => match content.to_sequence() {
+                Some(mut children) => Some(children.next().is_none()),
+                None => Some(false),
+            },
+            _ => Option::None,
+        }
+    }
+
+    /// Estimate the number of heap bytes owned by the value.
+    ///
+    /// Strings, arrays and dictionaries are traversed recursively, counting
+    /// data that is shared between multiple parts of the value only once.
+    /// Other values are treated as if they owned no heap memory.
+    pub fn heap_size(&self) -> usize {
+        self.heap_size_impl(&mut HashSet::new())
+    }
+
+    /// Estimate the heap size, skipping allocations that were already seen.
+    fn heap_size_impl(&self, seen: &mut HashSet<usize>) -> usize {
+        let value_size = std::mem::size_of::<Value>();
+        match self {
+            Self::Str(v) if seen.insert(v.as_ptr() as usize) => v.as_str().len(),
+            Self::Array(v) if seen.insert(v.as_slice().as_ptr() as usize) => {
+                v.iter().map(|item| value_size + item.heap_size_impl(seen)).sum()
+            }
+            // The address of the first key identifies the shared map.
+            Self::Dict(v) => match v.iter().next() {
+                Some((key, _)) if seen.insert(key as *const Str as usize) => {
+                    let mut size = 0;
+                    for (key, value) in v {
+                        size += std::mem::size_of::<Str>() + key.as_str().len();
+                        size += value_size + value.heap_size_impl(seen);
+                    }
+                    size
+                }
+                _ => 0,
+            },
+            _ => 0,
+        }
+    }
+
+    /// Whether the value counts as true when a boolean is needed.
+    ///
+    /// `none`, `false`, zero numbers and empty strings, arrays,
+    /// dictionaries and content are falsy, everything else is truthy. Note that
+    /// conditionals in the language itself still require booleans.
+    pub fn is_truthy(&self) -> bool {
+        match self {
+            Self::None => false,
+            Self::Bool(v) => *v,
+            Self::Int(v) => *v != 0,
+            Self::Float(v) => *v != 0.0,
+            Self::Length(v) => !v.is_zero(),
+            Self::Angle(v) => !v.is_zero(),
+            Self::Ratio(v) => !v.is_zero(),
+            Self::Relative(v) => !v.is_zero(),
+            Self::Fraction(v) => !v.is_zero(),
+            Self::Str(v) => !v.is_empty(),
+            Self::Content(v) => !v.is_empty(),
+            Self::Array(v) => !v.as_slice().is_empty(),
+            Self::Dict(v) => !v.is_empty(),
+            _ => true,
+        }
+    }
+
+    /// The number of non-collection values nested in the value.
+    ///
+    /// Arrays and dictionaries count as the sum of their items, so an empty
+    /// collection counts as zero. Any other value counts as one.
+    pub fn deep_len(&self) -> usize {
+        match self {
+            Self::Array(v) => v.iter().map(Self::deep_len).sum(),
+            Self::Dict(v) => v.iter().map(|(_, value)| value.deep_len()).sum(),
+            _ => 1,
+        }
+    }
+
     /// Return the debug representation of the value.
     pub fn repr(&self) -> Str {
         format_str!("{:?}", self)
@@ -198,6 +331,13 @@ impl Debug for Value {
     }
 }
 
+impl Display for Value {
+    /// Formats the value in the same way as its [representation](Self::repr).
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        Debug::fmt(self, f)
+    }
+}
+
 impl PartialEq for Value {
     fn eq(&self, other: &Self) -> bool {
         ops::equal(self, other)
@@ -210,6 +350,10 @@ impl PartialOrd for Value {
     }
 }
 
+/// Values are hashed by their exact representation, so values that compare
+/// equal, like `1` and `1.0` or `0.0` and `-0.0`, may still hash differently.
+/// Memoized function calls are keyed by this hash and must tell such values
+/// apart. Functions hash by identity, just like they compare.
 impl Hash for Value {
     fn hash<H: Hasher>(&self, state: &mut H) {
         std::mem::discriminant(self).hash(state);
@@ -240,6 +384,85 @@ impl Hash for Value {
     }
 }
 
+/// The kind of a value, without the value itself.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum ValueKind {
+    /// The kind of `none`.
+    None,
+    /// The kind of `auto`.
+    Auto,
+    /// A boolean.
+    Bool,
+    /// An integer.
+    Int,
+    /// A floating-point number.
+    Float,
+    /// A length.
+    Length,
+    /// An angle.
+    Angle,
+    /// A ratio.
+    Ratio,
+    /// A relative length.
+    Relative,
+    /// A fraction.
+    Fraction,
+    /// A color.
+    Color,
+    /// A symbol.
+    Symbol,
+    /// A string.
+    Str,
+    /// A label.
+    Label,
+    /// Content.
+    Content,
+    /// Content styles.
+    Styles,
+    /// An array.
+    Array,
+    /// A dictionary.
+    Dict,
+    /// A function.
+    Func,
+    /// Captured arguments to a function.
+    Args,
+    /// A module.
+    Module,
+    /// A dynamic value with the given type name.
+    Dyn(&'static str),
+}
+
+impl ValueKind {
+    /// The name of the kind, as returned by [`Value::type_name`].
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::None => "none",
+            Self::Auto => "auto",
+            Self::Bool => bool::TYPE_NAME,
+            Self::Int => i64::TYPE_NAME,
+            Self::Float => f64::TYPE_NAME,
+            Self::Length => Length::TYPE_NAME,
+            Self::Angle => Angle::TYPE_NAME,
+            Self::Ratio => Ratio::TYPE_NAME,
+            Self::Relative => Rel::<Length>::TYPE_NAME,
+            Self::Fraction => Fr::TYPE_NAME,
+            Self::Color => Color::TYPE_NAME,
+            Self::Symbol => Symbol::TYPE_NAME,
+            Self::Str => Str::TYPE_NAME,
+            Self::Label => Label::TYPE_NAME,
+            Self::Content => Content::TYPE_NAME,
+            Self::Styles => Styles::TYPE_NAME,
+            Self::Array => Array::TYPE_NAME,
+            Self::Dict => Dict::TYPE_NAME,
+            Self::Func => Func::TYPE_NAME,
+            Self::Args => Args::TYPE_NAME,
+            Self::Module => Module::TYPE_NAME,
+            Self::Dyn(name) => name,
+        }
+    }
+}
+
 /// A dynamic value.
 #[derive(Clone, Hash)]
 pub struct Dynamic(Arc<dyn Bounds>);
@@ -267,6 +490,17 @@ impl Dynamic {
     pub fn type_name(&self) -> &'static str {
         self.0.dyn_type_name()
     }
+
+    /// Add another value to this one through the type's [`Type::dyn_add`].
+    pub fn dyn_add(&self, rhs: &Value) -> Option<Value> {
+        self.0.dyn_add(rhs)
+    }
+
+    /// Compare this value with another one through the type's
+    /// [`Type::dyn_partial_cmp`].
+    pub fn dyn_partial_cmp(&self, other: &Value) -> Option<Ordering> {
+        self.0.dyn_partial_cmp(other)
+    }
 }
 
 impl Debug for Dynamic {
@@ -289,6 +523,8 @@ trait Bounds: Debug + Sync + Send + 'static {
     fn as_any(&self) -> &dyn Any;
     fn dyn_eq(&self, other: &Dynamic) -> bool;
     fn dyn_type_name(&self) -> &'static str;
+    fn dyn_add(&self, rhs: &Value) -> Option<Value>;
+    fn dyn_partial_cmp(&self, other: &Value) -> Option<Ordering>;
     fn hash128(&self) -> u128;
 }
 
@@ -309,6 +545,14 @@ where
         T::TYPE_NAME
     }
 
+    fn dyn_add(&self, rhs: &Value) -> Option<Value> {
+        <T as Type>::dyn_add(self, rhs)
+    }
+
+    fn dyn_partial_cmp(&self, other: &Value) -> Option<Ordering> {
+        <T as Type>::dyn_partial_cmp(self, other)
+    }
+
     fn hash128(&self) -> u128 {
         // Also hash the TypeId since values with different types but
         // equal data should be different.
@@ -326,9 +570,30 @@ impl Hash for dyn Bounds {
 }
 
 /// The type of a value.
+///
+/// Dynamic types can take part in addition and comparison by overriding the
+/// `dyn_` hooks. Since `cast_from_value!` implements this trait for named
+/// types, such types need to implement it by hand.
 pub trait Type {
     /// The name of the type.
     const TYPE_NAME: &'static str;
+
+    /// Add a value to this one with the `+` operator.
+    ///
+    /// The other value can be on either side of the operator, so the addition
+    /// must be commutative. Returns `None` if the operation is not supported.
+    fn dyn_add(&self, _rhs: &Value) -> Option<Value> {
+        None
+    }
+
+    /// Compare this value with another one with operators like `<`.
+    ///
+    /// The other value can be on either side of the operator. Returns `None`
+    /// if the values are incomparable. Values that compare as equal are also
+    /// considered equal by `==`.
+    fn dyn_partial_cmp(&self, _other: &Value) -> Option<Ordering> {
+        None
+    }
 }
 
 /// Implement traits for primitives.
@@ -445,4 +710,156 @@ mod tests {
         test(dict!["one" => 1], "(one: 1)");
         test(dict!["two" => false, "one" => 1], "(one: 1, two: false)");
     }
+
+    #[test]
+    fn test_value_display() {
+        let values = [
+            Value::Int(5),
+            Value::Str("hi".into()),
+            Value::Length(Abs::pt(1.5).into()),
+            Value::Array(array![1, "a"]),
+            Value::Dict(dict!["a" => Value::None]),
+        ];
+        for value in values {
+            assert_eq!(format!("{value}"), value.repr().as_str());
+        }
+    }
+
+    #[test]
+    fn test_value_type_name() {
+        #[derive(Debug, Clone, PartialEq, Hash)]
+        struct Widget;
+
+        impl Type for Widget {
+            const TYPE_NAME: &'static str = "widget";
+        }
+
+        assert_eq!(Value::Int(1).type_name(), "integer");
+        assert_eq!(Value::Array(array![]).type_name(), "array");
+        assert_eq!(Value::dynamic(Widget).type_name(), "widget");
+    }
+
+    #[test]
+    fn test_value_heap_size() {
+        let long = Str::from("a".repeat(10_000));
+        let size = Value::Str(long.clone()).heap_size();
+        assert!((10_000..10_100).contains(&size));
+
+        let shared = Value::Array(array![long.clone(), long.clone()]).heap_size();
+        assert!(shared < 2 * size);
+        let dict = Value::Dict(dict!["a" => long.clone(), "b" => long]).heap_size();
+        assert!(dict < 2 * size);
+
+        let nested = Value::Array(array![1, 2]);
+        let outer = Value::Array(array![nested.clone(), nested.clone()]);
+        assert_eq!(outer.heap_size(), 4 * std::mem::size_of::<Value>());
+        assert_eq!(Value::Int(1).heap_size(), 0);
+    }
+
+    #[test]
+    fn test_value_is_truthy() {
+        assert!(!Value::None.is_truthy());
+        assert!(!Value::Bool(false).is_truthy());
+        assert!(!Value::Int(0).is_truthy());
+        assert!(!Value::Float(0.0).is_truthy());
+        assert!(!Value::Length(Length::zero()).is_truthy());
+        assert!(!Value::Str("".into()).is_truthy());
+        assert!(!Value::Array(Array::new()).is_truthy());
+        assert!(!Value::Dict(Dict::new()).is_truthy());
+        assert!(!Value::Content(Content::empty()).is_truthy());
+        assert!(Value::Bool(true).is_truthy());
+        assert!(Value::Int(-1).is_truthy());
+        assert!(Value::Ratio(Ratio::one()).is_truthy());
+        assert!(Value::Str(" ".into()).is_truthy());
+        assert!(Value::Array(array![0]).is_truthy());
+        assert!(Value::Auto.is_truthy());
+    }
+
+    #[test]
+    fn test_value_deep_len() {
+        assert_eq!(Value::Int(1).deep_len(), 1);
+        assert_eq!(Value::Str("hello".into()).deep_len(), 1);
+        assert_eq!(Value::Array(Array::new()).deep_len(), 0);
+
+        let rows = array![
+            dict!["name" => "a", "tags" => array!["x", "y"]],
+            dict!["name" => "b", "tags" => Array::new()],
+            dict![],
+        ];
+        assert_eq!(Value::Array(rows).deep_len(), 4);
+    }
+
+    #[test]
+    fn test_cast_first_of() {
+        use crate::eval::cast_first_of;
+        let cast = |value: Value| cast_first_of!(value, Length, Ratio, Str);
+        assert_eq!(cast(Value::Ratio(Ratio::one())), Ok(Value::Ratio(Ratio::one())));
+        assert_eq!(cast(Value::Str("auto".into())), Ok(Value::Str("auto".into())));
+        assert_eq!(
+            cast(Value::Int(1)),
+            Err("expected length, ratio, or string, found integer".into())
+        );
+        assert_eq!(cast_first_of!(Value::Int(1), f64, i64), Ok(Value::Float(1.0)));
+    }
+
+    #[test]
+    fn test_value_hash() {
+        use crate::util::hash128;
+
+        #[track_caller]
+        fn test(a: impl Into<Value>, b: impl Into<Value>) {
+            let (a, b) = (a.into(), b.into());
+            assert_eq!(a, b);
+            assert_eq!(hash128(&a), hash128(&b));
+        }
+
+        test(1, 1);
+        test(1.5, 1.5);
+        test("a", "a");
+        test(array![1, 0.5], array![1, 0.5]);
+        test(dict!["a" => 0.5], dict!["a" => 0.5]);
+
+        // Values that compare equal but display differently must hash
+        // differently, because memoized function calls rely on the hash.
+        assert_ne!(hash128(&Value::Int(1)), hash128(&Value::Float(1.0)));
+        assert_ne!(hash128(&Value::Int(1)), hash128(&Value::Float(1.5)));
+        assert_ne!(hash128(&Value::Float(0.0)), hash128(&Value::Float(-0.0)));
+    }
+
+    #[test]
+    fn test_value_access() {
+        let dict = Value::Dict(dict!["one" => 1, "two" => "2"]);
+        assert_eq!(dict.get("one"), Some(&Value::Int(1)));
+        assert_eq!(dict.get("three"), None);
+        assert!(dict.contains_key("two"));
+        assert!(!dict.contains_key("three"));
+        assert!(!Value::Str("one".into()).contains_key("one"));
+
+        let array = Value::Array(array![1, 2, 3]);
+        assert_eq!(array.at(0), Some(Value::Int(1)));
+        assert_eq!(array.at(-1), Some(Value::Int(3)));
+        assert_eq!(array.at(-3), Some(Value::Int(1)));
+        assert_eq!(array.at(3), None);
+        assert_eq!(array.at(-4), None);
+        assert_eq!(array.get("one"), None);
+
+        let string = Value::Str("abc".into());
+        assert_eq!(string.at(1), Some(Value::Str("b".into())));
+        assert_eq!(string.at(-1), Some(Value::Str("c".into())));
+        assert_eq!(string.at(3), None);
+        assert_eq!(Value::Int(1).at(0), None);
+    }
+
+    #[test]
+    fn test_value_is_empty() {
+        assert_eq!(Value::Array(array![]).is_empty(), Some(true));
+        assert_eq!(Value::Array(array![0]).is_empty(), Some(false));
+        assert_eq!(Value::Dict(dict![]).is_empty(), Some(true));
+        assert_eq!(Value::Dict(dict!["a" => 1]).is_empty(), Some(false));
+        assert_eq!(Value::Str("".into()).is_empty(), Some(true));
+        assert_eq!(Value::Str(" ".into()).is_empty(), Some(false));
+        assert_eq!(Value::Content(Content::empty()).is_empty(), Some(true));
+        assert_eq!(Value::Int(0).is_empty(), None);
+        assert_eq!(Value::None.is_empty(), None);
+    }
 }
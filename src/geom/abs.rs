@@ -219,7 +219,7 @@ cast_to_value! {
 }
 
 /// Different units of absolute measurement.
-#[derive(Copy, Clone, Eq, PartialEq, Hash)]
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Cast)]
 pub enum AbsUnit {
     /// Points.
     Pt,
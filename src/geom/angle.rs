@@ -50,6 +50,13 @@ impl Angle {
         self.to_unit(AngleUnit::Deg)
     }
 
+    /// The equivalent angle in the range `[0deg, 360deg)`.
+    pub fn normalized(self) -> Self {
+        let rad = self.to_rad().rem_euclid(2.0 * PI);
+        // Tiny negative angles round up to a full turn.
+        Self::rad(if rad >= 2.0 * PI { 0.0 } else { rad })
+    }
+
     /// The absolute value of the this angle.
     pub fn abs(self) -> Self {
         Self::raw(self.to_raw().abs())
@@ -24,6 +24,16 @@ impl Length {
             .then(|| Self { abs: self.abs.abs(), em: self.em.abs() })
     }
 
+    /// Try to express the length as a number in the given absolute unit.
+    ///
+    /// Fails if the length has a font-relative part.
+    pub fn try_to_unit(self, unit: AbsUnit) -> StrResult<f64> {
+        if !self.em.is_zero() {
+            Err("cannot convert a length with font-relative units")?;
+        }
+        Ok(self.abs.to_unit(unit))
+    }
+
     /// Try to divide two lengths.
     pub fn try_div(self, other: Self) -> Option<f64> {
         if self.abs.is_zero() && other.abs.is_zero() {
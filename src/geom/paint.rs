@@ -1,5 +1,7 @@
 use std::str::FromStr;
 
+use ecow::{eco_format, EcoString};
+
 use super::*;
 
 /// How a fill or stroke should be painted.
@@ -63,6 +65,31 @@ impl Color {
     pub const GREEN: Self = Self::Rgba(RgbaColor::new(0x2E, 0xCC, 0x40, 0xFF));
     pub const LIME: Self = Self::Rgba(RgbaColor::new(0x01, 0xFF, 0x70, 0xFF));
 
+    /// The predefined color with the given name, like `red` or `navy`.
+    pub fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "black" => Self::BLACK,
+            "gray" => Self::GRAY,
+            "silver" => Self::SILVER,
+            "white" => Self::WHITE,
+            "navy" => Self::NAVY,
+            "blue" => Self::BLUE,
+            "aqua" => Self::AQUA,
+            "teal" => Self::TEAL,
+            "eastern" => Self::EASTERN,
+            "purple" => Self::PURPLE,
+            "fuchsia" => Self::FUCHSIA,
+            "maroon" => Self::MAROON,
+            "red" => Self::RED,
+            "orange" => Self::ORANGE,
+            "yellow" => Self::YELLOW,
+            "olive" => Self::OLIVE,
+            "green" => Self::GREEN,
+            "lime" => Self::LIME,
+            _ => return None,
+        })
+    }
+
     /// Convert this color to RGBA.
     pub fn to_rgba(self) -> RgbaColor {
         match self {
@@ -98,6 +125,24 @@ impl Color {
             Self::Cmyk(cmyk) => Self::Cmyk(cmyk.negate()),
         }
     }
+
+    /// Mix this color with another one in RGBA space.
+    pub fn mix(self, other: Self, t: f64) -> Self {
+        Self::Rgba(self.to_rgba().mix(other.to_rgba(), t))
+    }
+}
+
+impl FromStr for Color {
+    type Err = &'static str;
+
+    /// Constructs a color from a hex string with a leading hashtag, like
+    /// `#ff4136`, or from the name of a predefined color, like `red`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.strip_prefix('#') {
+            Some(hex) => hex.parse::<RgbaColor>().map(Self::Rgba),
+            None => Self::from_name(s).ok_or("unknown color name"),
+        }
+    }
 }
 
 impl Debug for Color {
@@ -184,6 +229,54 @@ impl RgbaColor {
         Self { r, g, b, a }
     }
 
+    /// Construct an opaque RGBA color from hue, saturation and lightness.
+    pub fn from_hsl(hue: Angle, saturation: Ratio, lightness: Ratio) -> Self {
+        let h = hue.to_deg().rem_euclid(360.0) / 60.0;
+        let s = saturation.get().clamp(0.0, 1.0);
+        let l = lightness.get().clamp(0.0, 1.0);
+        let c = (1.0 - (2.0 * l - 1.0).abs()) * s;
+        let x = c * (1.0 - (h % 2.0 - 1.0).abs());
+        let (r, g, b) = match h as u8 {
+            0 => (c, x, 0.0),
+            1 => (x, c, 0.0),
+            2 => (0.0, c, x),
+            3 => (0.0, x, c),
+            4 => (x, 0.0, c),
+            _ => (c, 0.0, x),
+        };
+        let m = l - c / 2.0;
+        let channel = |v: f64| round_u8((v + m) * 255.0);
+        Self::new(channel(r), channel(g), channel(b), u8::MAX)
+    }
+
+    /// Convert to hue, saturation and lightness.
+    ///
+    /// The alpha channel is ignored. Achromatic colors have a hue and
+    /// saturation of zero.
+    pub fn to_hsl(self) -> (Angle, Ratio, Ratio) {
+        let r = self.r as f64 / 255.0;
+        let g = self.g as f64 / 255.0;
+        let b = self.b as f64 / 255.0;
+        let max = r.max(g).max(b);
+        let min = r.min(g).min(b);
+        let l = (max + min) / 2.0;
+        let d = max - min;
+        if d == 0.0 {
+            return (Angle::zero(), Ratio::zero(), Ratio::new(l));
+        }
+
+        let s = d / (1.0 - (2.0 * l - 1.0).abs());
+        let h = if max == r {
+            ((g - b) / d).rem_euclid(6.0)
+        } else if max == g {
+            (b - r) / d + 2.0
+        } else {
+            (r - g) / d + 4.0
+        };
+
+        (Angle::deg(h * 60.0), Ratio::new(s), Ratio::new(l))
+    }
+
     /// Lighten this color by a factor.
     ///
     /// The alpha channel is not affected.
@@ -222,6 +315,96 @@ impl RgbaColor {
             a: self.a,
         }
     }
+
+    /// Linearly interpolate all channels, including alpha, between this and
+    /// another color.
+    ///
+    /// A factor of zero yields this color and a factor of one the other color.
+    /// Factors outside of this range are clamped.
+    pub fn mix(self, other: Self, t: f64) -> Self {
+        let t = t.clamp(0.0, 1.0);
+        let mix = |a: u8, b: u8| round_u8(a as f64 + (b as f64 - a as f64) * t);
+        Self {
+            r: mix(self.r, other.r),
+            g: mix(self.g, other.g),
+            b: mix(self.b, other.b),
+            a: mix(self.a, other.a),
+        }
+    }
+
+    /// Set the alpha channel to the given ratio of full opacity.
+    ///
+    /// Ratios outside of the range from zero to one are clamped.
+    pub fn with_alpha(self, alpha: Ratio) -> Self {
+        let a = round_u8(alpha.get().clamp(0.0, 1.0) * 255.0);
+        Self { a, ..self }
+    }
+
+    /// Scale the alpha channel by the given factor.
+    ///
+    /// Factors outside of the range from zero to one are clamped, so the color
+    /// never becomes more opaque.
+    pub fn fade(self, factor: Ratio) -> Self {
+        let a = round_u8(self.a as f64 * factor.get().clamp(0.0, 1.0));
+        Self { a, ..self }
+    }
+
+    /// Multiply the color channels with the alpha channel.
+    ///
+    /// The alpha channel is not affected.
+    pub fn premultiply(self) -> Self {
+        let mul = |c: u8| round_u8(c as f64 * self.a as f64 / 255.0);
+        Self {
+            r: mul(self.r),
+            g: mul(self.g),
+            b: mul(self.b),
+            a: self.a,
+        }
+    }
+
+    /// Divide the color channels by the alpha channel, reversing
+    /// [`premultiply`](Self::premultiply).
+    ///
+    /// The alpha channel is not affected. A fully transparent color becomes
+    /// transparent black.
+    pub fn unpremultiply(self) -> Self {
+        if self.a == 0 {
+            return Self::new(0, 0, 0, 0);
+        }
+
+        let div = |c: u8| round_u8(c as f64 * 255.0 / self.a as f64);
+        Self {
+            r: div(self.r),
+            g: div(self.g),
+            b: div(self.b),
+            a: self.a,
+        }
+    }
+
+    /// Format as a hexadecimal string like `#rrggbb`.
+    ///
+    /// The alpha channel is appended as `#rrggbbaa` unless the color is
+    /// opaque.
+    pub fn to_hex(self) -> EcoString {
+        let mut hex = eco_format!("#{:02x}{:02x}{:02x}", self.r, self.g, self.b);
+        if self.a != u8::MAX {
+            hex.push_str(&eco_format!("{:02x}", self.a));
+        }
+        hex
+    }
+
+    /// Format as a CSS-style `rgb(r, g, b)` string.
+    ///
+    /// Colors that are not opaque are formatted as `rgba(r, g, b, a)` with
+    /// the alpha channel ranging from zero to one.
+    pub fn to_rgb_string(self) -> EcoString {
+        if self.a == u8::MAX {
+            eco_format!("rgb({}, {}, {})", self.r, self.g, self.b)
+        } else {
+            let alpha = (self.a as f64 / 255.0 * 1000.0).round() / 1000.0;
+            eco_format!("rgba({}, {}, {}, {})", self.r, self.g, self.b, alpha)
+        }
+    }
 }
 
 impl FromStr for RgbaColor {
@@ -412,4 +595,59 @@ mod tests {
         test("hmmm", "color string contains non-hexadecimal letters");
         test("14B2AH", "color string contains non-hexadecimal letters");
     }
+
+    #[test]
+    fn test_parse_color_names() {
+        assert_eq!(Color::from_str("#ff4136"), Ok(Color::RED));
+        assert_eq!(Color::from_str("red"), Ok(Color::RED));
+        assert_eq!(Color::from_str("navy"), Ok(Color::NAVY));
+        assert_eq!(Color::from_str("ff4136"), Err("unknown color name"));
+        assert_eq!(Color::from_str("Red"), Err("unknown color name"));
+        assert_eq!(
+            Color::from_str("#red"),
+            Err("color string contains non-hexadecimal letters")
+        );
+    }
+
+    #[test]
+    fn test_hsl_round_trip() {
+        #[track_caller]
+        fn test(r: u8, g: u8, b: u8) {
+            let color = RgbaColor::new(r, g, b, 0xff);
+            let (h, s, l) = color.to_hsl();
+            assert_eq!(RgbaColor::from_hsl(h, s, l), color);
+        }
+
+        test(0xff, 0x00, 0x00);
+        test(0x00, 0x80, 0x00);
+        test(0x80, 0x80, 0x80);
+        test(0x23, 0x9d, 0xad);
+        test(0xf0, 0x12, 0xbe);
+        test(0xff, 0xdc, 0x00);
+    }
+
+    #[test]
+    fn test_premultiply_round_trip() {
+        #[track_caller]
+        fn test(r: u8, g: u8, b: u8, a: u8) {
+            let color = RgbaColor::new(r, g, b, a);
+            let back = color.premultiply().unpremultiply();
+            let close = |x: u8, y: u8| x.abs_diff(y) <= 255 / a;
+            assert!(close(back.r, r) && close(back.g, g) && close(back.b, b));
+            assert_eq!(back.a, a);
+        }
+
+        test(0xff, 0x80, 0x00, 0xff);
+        test(0xff, 0x80, 0x00, 0x80);
+        test(0x23, 0x9d, 0xad, 0x40);
+        test(0x10, 0x20, 0x30, 0x01);
+        assert_eq!(
+            RgbaColor::new(0xff, 0x80, 0x00, 0x80).premultiply(),
+            RgbaColor::new(0x80, 0x40, 0x00, 0x80)
+        );
+        assert_eq!(
+            RgbaColor::new(0xff, 0x80, 0x00, 0).unpremultiply(),
+            RgbaColor::new(0, 0, 0, 0)
+        );
+    }
 }
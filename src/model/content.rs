@@ -7,7 +7,7 @@ use ecow::{eco_format, EcoString, EcoVec};
 
 use super::{
     element, Behave, Behaviour, ElemFunc, Element, Fold, Guard, Label, Locatable,
-    Location, Recipe, Style, Styles, Synthesize,
+    Location, Recipe, Selector, Style, Styles, Synthesize,
 };
 use crate::diag::{SourceResult, StrResult};
 use crate::doc::Meta;
@@ -347,6 +347,29 @@ impl Content {
     pub fn set_location(&mut self, location: Location) {
         self.attrs.push(Attr::Location(location));
     }
+
+    /// Count how many elements in this content match the selector, including
+    /// the content itself and everything nested in its fields.
+    pub fn count(&self, selector: &Selector) -> usize {
+        let mut count = usize::from(selector.matches(self));
+        for attr in &self.attrs {
+            count += match attr {
+                Attr::Child(child) => child.count(selector),
+                Attr::Value(value) => count_in_value(value, selector),
+                _ => 0,
+            };
+        }
+        count
+    }
+}
+
+/// Count the matching elements in content nested in a field value.
+fn count_in_value(value: &Value, selector: &Selector) -> usize {
+    match value {
+        Value::Content(content) => content.count(selector),
+        Value::Array(array) => array.iter().map(|v| count_in_value(v, selector)).sum(),
+        _ => 0,
+    }
 }
 
 impl Debug for Content {
@@ -106,6 +106,25 @@
   test(tasks.at("b"), (4, 5, 6, 7))
 }
 
+---
+// Test that mutating a copy leaves the original untouched.
+#{
+  let a = (1, 2)
+  let b = a
+  b.push(3)
+  test(b.pop(), 3)
+  b.insert(0, 0)
+  test(a, (1, 2))
+  test(b, (0, 1, 2))
+}
+
+---
+// Error: 3:3-3:14 array is empty
+#{
+  let array = ()
+  array.pop()
+}
+
 ---
 // Test the `insert` and `remove` methods.
 #{
@@ -116,6 +135,13 @@
   test(array, (0, 2, 3, 4, 5))
 }
 
+---
+// Error: 3:3-3:21 array index out of bounds (index: 4, len: 3)
+#{
+  let array = (1, 2, 3)
+  array.insert(4, 4)
+}
+
 ---
 // Error: 2:16-2:18 missing argument: index
 #let numbers = ()
@@ -130,6 +156,9 @@
 #test((1, 2, 3).slice(2, -2), ())
 #test((1, 2, 3).slice(-2, 2), (2,))
 #test((1, 2, 3).slice(-3, 2), (1, 2))
+#test((1, 2, 3).slice(-2), (2, 3))
+#test((1, 2, 3).slice(1, 1), ())
+#test((1, 2, 3).slice(3), ())
 #test("ABCD".split("").slice(1, -1).join("-"), "A-B-C-D")
 
 ---
@@ -145,6 +174,76 @@
 #test(("Hi", "❤️", "Love").position(s => s == "❤️"), 1)
 #test(("Bye", "💘", "Apart").position(s => s == "❤️"), none)
 #test(("A", "B", "CDEF", "G").position(v => v.len() > 2), 2)
+#test(().position(v => true), none)
+
+---
+// Test the `any` and `all` methods.
+#test((1, 2, 3).any(v => v > 2), true)
+#test((1, 2, 3).any(v => v > 3), false)
+#test((1, 2, 3).all(v => v > 0), true)
+#test((1, 2, 3).all(v => v > 1), false)
+#test(().any(v => true), false)
+#test(().all(v => false), true)
+
+---
+// Test that `any` and `all` stop at the first deciding item. The later items
+// would make the predicate fail.
+#test((true, "no").any(v => v), true)
+#test((false, "no").all(v => v), false)
+
+---
+// Error: 20-21 expected boolean, found string
+#(false, "no").any(v => v)
+
+---
+// Test the `find` method.
+#test((1, 2, 3, 4).find(v => calc.even(v)), 2)
+#test((1, 3, 5).find(v => calc.even(v)), none)
+#test(((a: 1), (a: 2)).find(d => d.a > 1), (a: 2))
+#test((1, "a").find(v => v == 1 or v.len() > 0), 1)
+
+---
+// Error: 14-15 expected boolean, found integer
+#(1, 2).find(v => v)
+
+---
+// Error: 18-19 expected boolean, found string
+#("a",).position(v => v)
+
+---
+// Test the `is-subset` and `is-superset` methods.
+#test((1, 2).is-subset((3, 2, 1)), true)
+#test((1, 4).is-subset((3, 2, 1)), false)
+#test(().is-subset(()), true)
+#test((1, 1).is-subset((1, 2)), true)
+#test((1, 1).is-subset((1, 2), multiset: true), false)
+#test((1, 2, 1).is-subset((1, 2, 1, 3), multiset: true), true)
+#test((3, 2, 1).is-superset((1, 2)), true)
+#test((1, 2).is-superset((1, 2, 2), multiset: true), false)
+
+---
+// Error: 17-18 expected array, found integer
+#(1,).is-subset(1)
+
+---
+// Test the `first-where` and `last-where` methods.
+#test((1, 2, 3, 4).first-where(v => calc.even(v)), (2, 1))
+#test((1, 2, 3, 4).last-where(v => calc.even(v)), (4, 3))
+#test((1, 3, 5).first-where(v => calc.even(v)), none)
+#test(().last-where(v => true), none)
+#{
+  let words = ("apple", "kiwi", "banana", "fig", "cherry")
+  let first = words.first-where(w => w.len() > 5)
+  test(words.at(first.at(1)), first.at(0))
+  test(first.at(0), "banana")
+  let last = words.last-where(w => w.len() < 5)
+  test(words.at(last.at(1)), last.at(0))
+  test(last.at(1), 3)
+}
+
+---
+// Error: 21-22 expected boolean, found integer
+#(1, 2).first-where(v => v + 1)
 
 ---
 // Test the `filter` method.
@@ -170,6 +269,179 @@
 // Test the `rev` method.
 #test(range(3).rev(), (2, 1, 0))
 
+---
+// Test the `flatten` method.
+#test(().flatten(), ())
+#test((1, (2, (3, (4,)))).flatten(), (1, 2, 3, 4))
+#test((1, (2, (3, (4,)))).flatten(depth: 1), (1, 2, (3, (4,))))
+#test((1, (2, (3, (4,)))).flatten(depth: 2), (1, 2, 3, (4,)))
+#test(((1, 2), 3, ("a",)).flatten(depth: 0), ((1, 2), 3, ("a",)))
+
+---
+// Test the `zip` method.
+#test((1, 2, 3).zip(("a", "b", "c")), ((1, "a"), (2, "b"), (3, "c")))
+#test((1, 2, 3).zip(("a",)), ((1, "a"),))
+#test(("a",).zip((1, 2, 3)), (("a", 1),))
+#test(().zip((1, 2)), ())
+#test((1, 2).zip((3, 4)).unzip(), ((1, 2), (3, 4)))
+
+---
+// Error: 11-15 expected array, found string
+#(1,).zip("ab")
+
+---
+// Test the `enumerate` method.
+#test(().enumerate(), ())
+#test(("a", "b", "c").enumerate(), ((0, "a"), (1, "b"), (2, "c")))
+#test(("a", "b").enumerate(start: 1), ((1, "a"), (2, "b")))
+#test((1, 2).enumerate(start: -1), ((-1, 1), (0, 2)))
+#for i, pair in ("x", "y").enumerate(start: 3) {
+  test(pair.first(), i + 3)
+}
+
+---
+// Error: 26-29 expected integer, found string
+#(1, 2).enumerate(start: "1")
+
+---
+#test((1,).enumerate(start: 9223372036854775807), ((9223372036854775807, 1),))
+
+// Error: 2-46 array index is too large
+#(1, 2).enumerate(start: 9223372036854775807)
+
+---
+// Test the `unique` method.
+#test((3, 1, 3, 2, 1).unique(), (3, 1, 2))
+#test(().unique(), ())
+#test((1, 1.0, "1").unique(), (1, "1"))
+#test(((a: 1), (a: 1), (a: 2)).unique(), ((a: 1), (a: 2)))
+#test(("apple", "avocado", "banana", "blueberry").unique(key: s => s.first()), ("apple", "banana"))
+#test((-2, 1, 2, -1).unique(key: calc.abs), (-2, 1))
+#{
+  let f(x) = x
+  let g(x) = x
+  test((f, f, g).unique().len(), 2)
+}
+
+---
+// Test the `sum` and `product` methods.
+#test((1, 2, 3).sum(), 6)
+#test((1, 2.5).sum(), 3.5)
+#test(type((1, 2.0).sum()), "float")
+#test((1pt, 2cm, 3mm).sum(), 1pt + 2cm + 3mm)
+#test((1pt, 2em).sum(), 1pt + 2em)
+#test(("a", "b").sum(), "ab")
+#test(().sum(), 0)
+#test((2, 3, 4).product(), 24)
+#test((2, 0.5).product(), 1.0)
+#test((2pt, 3).product(), 6pt)
+#test(().product(), 1)
+#test(("a", "bb", "ccc").sum(key: s => s.len()), 6)
+#test(((x: 2), (x: 5)).product(key: d => d.x), 10)
+
+---
+// Error: 2-20 cannot add length and angle
+#(1pt, 90deg).sum()
+
+---
+// Error: 2-22 cannot multiply length with length
+#(1pt, 2pt).product()
+
+---
+// Test the `running-max` and `running-min` methods.
+#test(().running-max(), ())
+#test((1, 3, 2, 5).running-max(), (1, 3, 3, 5))
+#test((4, 5, 2, 3, 1).running-min(), (4, 4, 2, 2, 1))
+#test((1, 2.5, 2).running-max(), (1, 2.5, 2.5))
+#test((1pt, 2cm, 1mm).running-max(), (1pt, 2cm, 2cm))
+#test(("b", "a", "c").running-min(), ("b", "a", "a"))
+
+---
+// Error: 2-27 cannot compare integer and string
+#(1, "a", 2).running-max()
+
+---
+// Test the `equal-ignoring` method.
+#let runs = ((id: 1, time: 20), (id: 2, time: 30))
+#test(runs.equal-ignoring(((id: 1, time: 25), (id: 2, time: 35)), ((0, "time"), (1, "time"))), true)
+#test(runs.equal-ignoring(((id: 1, time: 25), (id: 3, time: 30)), ((0, "time"),)), false)
+#test((1, 2, 3).equal-ignoring((1, 5, 3), ((1,),)), true)
+#test((1, 2, 3).equal-ignoring((1, 5, 3), ((-2,),)), true)
+#test((1, 2, 3).equal-ignoring((1, 5), ((1,),)), false)
+
+---
+// Test the `reorder` method.
+#test((10, 20, 30).reorder((2, 0, 1)), (30, 10, 20))
+#test((10, 20, 30).reorder((-1, 0, 0)), (30, 10, 10))
+#test((10, 20, 30).reorder(()), ())
+#{
+  let names = ("c", "a", "b")
+  let ages = (3, 1, 2)
+  test(ages.reorder(names.argsort()), (1, 2, 3))
+}
+
+---
+// Error: 2-30 array index out of bounds (index: 3, len: 3)
+#(10, 20, 30).reorder((0, 3))
+
+---
+// Error: 2-26 expected integer, found string
+#(10, 20).reorder(("a",))
+
+---
+// Test the `common-prefix` and `common-suffix` methods.
+#test(("foobar", "foobaz").common-prefix(), "fooba")
+#test(("foobar", "barbar", "bar").common-suffix(), "bar")
+#test(("abc", "xyz").common-prefix(), "")
+#test(("single",).common-prefix(), "single")
+#test(("single",).common-suffix(), "single")
+#test(("äöü", "äöa").common-prefix(), "äö")
+#test(("aé", "eé").common-suffix(), "é")
+
+---
+// Error: 2-20 array is empty
+#().common-prefix()
+
+---
+// Error: 2-26 expected string, found integer
+#("a", 1).common-suffix()
+
+---
+// Test the `unzip` method.
+#test(().unzip(), ())
+#test(((1, "a"), (2, "b")).unzip(), ((1, 2), ("a", "b")))
+#test(((1, 2, 3),).unzip(), ((1,), (2,), (3,)))
+
+---
+// Error: 2-26 array lengths differ (expected 2, found 1)
+#((1, "a"), (2,)).unzip()
+
+---
+// Error: 2-21 expected array, found integer
+#((1, 2), 3).unzip()
+
+---
+// Test the `chunks` and `windows` methods.
+#test(().chunks(2), ())
+#test((1, 2, 3, 4).chunks(2), ((1, 2), (3, 4)))
+#test((1, 2, 3, 4, 5).chunks(2), ((1, 2), (3, 4), (5,)))
+#test((1, 2).chunks(3), ((1, 2),))
+#test((1, 2, 3).windows(2), ((1, 2), (2, 3)))
+#test((1, 2, 3).windows(3), ((1, 2, 3),))
+#test((1, 2).windows(3), ())
+
+---
+// Error: 19-20 number must be positive
+#(1, 2, 3).chunks(0)
+
+---
+// Error: 20-22 number must be positive
+#(1, 2, 3).windows(-1)
+
+---
+// Error: 18-20 missing argument: chunk size
+#(1, 2, 3).chunks()
+
 ---
 // Test the `join` method.
 #test(().join(), none)
@@ -201,6 +473,24 @@
 // Error: 2-26 cannot order content and content
 #([Hi], [There]).sorted()
 
+---
+// Test the `argsort` method.
+#test(().argsort(), ())
+#test((3, 1, 2).argsort(), (1, 2, 0))
+#test(("b", "a", "c").argsort(key: s => -s.len()), (0, 1, 2))
+#test((3, -1, 2, -3).argsort(key: calc.abs), (1, 2, 0, 3))
+#{
+  let values = (5, 2, 8, 1, 9, 2)
+  let names = ("e", "b", "h", "a", "i", "c")
+  let order = values.argsort()
+  test(order.map(i => values.at(i)), values.sorted())
+  test(order.map(i => names.at(i)), ("a", "b", "c", "e", "h", "i"))
+}
+
+---
+// Error: 2-20 cannot order string and integer
+#(1, "a").argsort()
+
 ---
 // Error: 2-18 array index out of bounds (index: -4, len: 3)
 #(1, 2, 3).at(-4)
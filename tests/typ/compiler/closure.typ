@@ -46,6 +46,33 @@
   test(greet("Typst"), "Hi, Typst!")
 }
 
+---
+// Captures in markup are also taken at definition time.
+#let x = 1
+#let f() = x
+#let items = (1,)
+#let g() = items.len()
+#{ x = 2 }
+#{ items.push(2) }
+#test(f(), 1)
+#test(g(), 1)
+#test(x, 2)
+
+---
+// Memoized calls must not mix up arguments that compare equal across types.
+#{
+  let f(x) = type(x)
+  let out = ()
+  for x in (1, 1.0, 0%, 0pt + 0%) {
+    out.push(f(x))
+  }
+  test(out, ("integer", "float", "ratio", "relative length"))
+}
+
+---
+// Memoized calls must not mix up zeros of different signs.
+#test((0.0, -0.0).map(x => repr(x)), ("0.0", "-0.0"))
+
 ---
 // Redefined variable.
 #{
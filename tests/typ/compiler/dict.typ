@@ -55,6 +55,82 @@
 #test("c" in dict, false)
 #test(dict, (a: 3, b: 1))
 
+---
+// Test that iteration order is sorted by key.
+#let dict = (zeta: 1, Alpha: 2, beta: 3, "10": 4, "9": 5)
+#test(dict.keys(), ("10", "9", "Alpha", "beta", "zeta"))
+#test(dict.values(), (4, 5, 2, 3, 1))
+#test(dict.pairs(), (("10", 4), ("9", 5), ("Alpha", 2), ("beta", 3), ("zeta", 1)))
+#test(dict.pairs().map(p => p.first()), dict.keys())
+#test((:).keys(), ())
+#test((:).pairs(), ())
+#{
+  let dict = (b: 1)
+  dict.insert("a", 2)
+  dict.c = 3
+  test(dict.keys(), ("a", "b", "c"))
+  let keys = ()
+  for k, v in dict {
+    keys.push(k)
+  }
+  test(keys, dict.keys())
+}
+
+---
+// Error: 2-15 type array has no method `keys`
+#(1, 2).keys()
+
+---
+// Test the `equal-ignoring` method.
+#let a = (name: "Typst", id: 1, meta: (date: "today", tags: ("a", "b")))
+#let b = (name: "Typst", id: 2, meta: (date: "yesterday", tags: ("a", "c")))
+#test(a.equal-ignoring(b, ()), false)
+#test(a.equal-ignoring(b, (("id",), ("meta",))), true)
+#test(a.equal-ignoring(b, (("id",), ("meta", "date"))), false)
+#test(a.equal-ignoring(b, (("id",), ("meta", "date"), ("meta", "tags", 1))), true)
+#test(a.equal-ignoring(b, (("id",), ("meta", "date"), ("meta", "tags", -1))), true)
+#test(a.equal-ignoring(b, (("meta",),)), false)
+#test((a: 1, t: 2).equal-ignoring((a: 1), (("t",),)), true)
+#test((a: 1, t: 2).equal-ignoring((a: 1), ()), false)
+#test((a: 1).equal-ignoring((a: 1.0), ()), true)
+#test((a: 1).equal-ignoring(1, ()), false)
+#test(a.equal-ignoring(5, ((),)), true)
+
+---
+// Error: 26-32 expected array, found string
+#(:).equal-ignoring((:), ("a",))
+
+---
+// Test the `is-subset` and `is-superset` methods.
+#test((a: 1).is-subset((a: 1, b: 2)), true)
+#test((a: 1, b: 2).is-subset((a: 1, b: 2)), true)
+#test((a: 2).is-subset((a: 1, b: 2)), false)
+#test((c: 1).is-subset((a: 1, b: 2)), false)
+#test((:).is-subset((:)), true)
+#test((a: 1, b: 2).is-superset((b: 2)), true)
+#test((a: 1).is-superset((a: 1, b: 2)), false)
+
+---
+// Test merging dictionaries.
+#let base = (title: "Base", page: (width: 10cm, margin: 1cm), tags: ("a", "b"))
+#let extra = (page: (margin: 2cm), tags: ("b", "c"), draft: true)
+#test(
+  base.merge(extra),
+  (title: "Base", page: (width: 10cm, margin: 2cm), tags: ("b", "c"), draft: true),
+)
+#test(base.merge(extra, array-strategy: "concat").tags, ("a", "b", "b", "c"))
+#test(base.merge(extra, array-strategy: "union").tags, ("a", "b", "c"))
+#test(
+  (x: (y: (1,))).merge((x: (y: (2,))), array-strategy: "concat"),
+  (x: (y: (1, 2))),
+)
+#test((a: (1,)).merge((a: 2), array-strategy: "concat"), (a: 2))
+#test(base.merge((:)), base)
+
+---
+// Error: 36-44 expected "replace", "concat", or "union"
+#(a: 1).merge((:), array-strategy: "append")
+
 ---
 // Error: 24-29 duplicate key
 #(first: 1, second: 2, first: 3)
@@ -26,6 +26,59 @@
   test(rewritten, "Hello!\n This is a sentence!\n And one more!")
 }
 
+---
+// Test decomposing relative lengths.
+#test((50% + 2cm).rel-part(), 50%)
+#test((50% + 2cm).abs-part(), 2cm)
+#test((10% - 1em).abs-part(), -1em)
+#test((25%).rel-part(), 25%)
+#test((25%).abs-part(), 0pt)
+#test((3pt).rel-part(), 0%)
+#test((3pt).abs-part(), 3pt)
+
+---
+// Test converting lengths.
+#test((72pt).to-in(), 1.0)
+#test((1in).to-pt(), 72.0)
+#assert(calc.abs((10mm).to-cm() - 1.0) < 1e-9)
+#test(calc.round((2.54cm).to-in(), digits: 4), 1.0)
+#assert(calc.abs((1cm).to-mm() - 10.0) < 1e-9)
+#assert(calc.abs((10mm).convert("cm") - 1.0) < 1e-9)
+#test((0pt).convert("in"), 0.0)
+#test((1em - 1em + 3pt).to-pt(), 3.0)
+
+---
+// Error: 2-15 cannot convert a length with font-relative units
+#(1em).to-pt()
+
+---
+// Error: 16-20 expected "pt", "mm", "cm", or "in"
+#(1cm).convert("km")
+
+---
+// Test converting and normalizing angles.
+#test((180deg).to-deg(), 180.0)
+#test((1rad).to-rad(), 1.0)
+#test(calc.round((90deg).to-rad(), digits: 4), 1.5708)
+#test(calc.round((450deg).normalize().to-deg(), digits: 6), 90.0)
+#test(calc.round((-90deg).normalize().to-deg(), digits: 6), 270.0)
+#test(calc.round((-720deg - 45deg).normalize().to-deg(), digits: 6), 315.0)
+#test((360deg).normalize(), 0deg)
+#test((0deg).normalize(), 0deg)
+#test((-1e-20deg).normalize(), 0deg)
+
+---
+// Test resolving relative lengths.
+#test((50% + 10pt).resolve(100pt), 60pt)
+#test((50%).resolve(100pt), 50pt)
+#test((-25% + 10pt).resolve(20pt), 5pt)
+#test((100% - 1em).resolve(2em), 1em)
+#test((5pt).resolve(1cm), 5pt)
+
+---
+// Error: 16-19 expected length, found ratio
+#(50%).resolve(10%)
+
 ---
 // Error: 2:2-2:15 type array has no method `fun`
 #let numbers = ()
@@ -44,3 +97,28 @@
 ---
 // Error: 2-5 cannot mutate a constant
 #box.push(1)
+
+---
+// Test counting elements in content.
+#test([*a* *b*].count(strong), 2)
+#test([*a* _*b*_ c].count(strong), 2)
+#test([*a*].count(strong), 1)
+#test([a b].count(strong), 0)
+#test([a *b* a].count("a"), 2)
+#test([*a* <x> b].count(<x>), 1)
+#{
+  let body = [
+    = A
+    - one
+    - *two*
+    == B
+    = C
+  ]
+  test(body.count(strong), 1)
+  test(body.count(heading), 3)
+  test(body.count(heading.where(level: 1)), 2)
+}
+
+---
+// Error: 14-15 expected function, label, string, regular expression, or selector, found integer
+#[*a*].count(1)
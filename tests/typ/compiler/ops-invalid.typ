@@ -50,6 +50,10 @@
 // Error: 3-10 cannot add integer and string
 #(1 + "2", 40% - 1)
 
+---
+// Error: 3-10 cannot add content and integer
+#([a] + 1)
+
 ---
 // Error: 15-23 cannot add integer and string
 #{ let x = 1; x += "2" }
@@ -86,6 +90,10 @@
 // Error: 3-10 cannot repeat this string -1 times
 #(-1 * "")
 
+---
+// Error: 3-11 cannot repeat this content -2 times
+#([a] * -2)
+
 ---
 #{
   let x = 2
@@ -6,6 +6,25 @@
 // Ref: true
 #([*Hello* ] + [world!])
 
+---
+// Test that adding content concatenates the children.
+#let a = [*Hello* there]
+#let b = [_dear_ world]
+#test((a + b).children.len(), a.children.len() + b.children.len())
+#test((a + b).children, a.children + b.children)
+#test(([] + []).children, ())
+#test(type("Hi" + [there]), "content")
+#test(type([Hi] + "there"), "content")
+#test(("Hi" + a).children.len(), a.children.len() + 1)
+#test((a + "there").children.len(), a.children.len() + 1)
+
+---
+// Test repeating content.
+#test(([Hi] * 0).children, ())
+#test(([Hi] * 1), [Hi])
+#test(([Hi] * 3).children, ([Hi], [Hi], [Hi]))
+#test((2 * [*Hi* there]).children.len(), 2)
+
 ---
 // Test math operators.
 
@@ -32,6 +51,8 @@
 #test(13 * "a" + "bbbbbb", "aaaaaaaaaaaaabbbbbb")
 #test((1, 2) + (3, 4), (1, 2, 3, 4))
 #test((a: 1) + (b: 2, c: 3), (a: 1, b: 2, c: 3))
+#test(50% + 60%, 110%)
+#test(calc.clamp(50% + 60%, 0%, 100%), 100%)
 
 // Subtraction.
 #test(1-4, 3*-1)
@@ -40,6 +61,9 @@
 
 // Multiplication.
 #test(2 * 4, 8)
+#test(50% * 50%, 25%)
+#test(200% * 5%, 10%)
+#test(50% * 0.5, 25%)
 
 // Division.
 #test(12pt/.4, 30pt)
@@ -109,6 +133,18 @@
   }
 }
 
+---
+// Test that integer overflow produces a float.
+#let max = 9223372036854775807
+#let min = -max - 1
+#test(type(max + 1), "float")
+#test(max + 1, 9223372036854775808.0)
+#test(type(min - 1), "float")
+#test(max * 2, 18446744073709551614.0)
+#test(-min, 9223372036854775808.0)
+#test(type(max - 1), "integer")
+#test(type(min + max), "integer")
+
 ---
 // Test boolean operators.
 
@@ -160,6 +196,20 @@
 #test(grid[a] == grid[a], true)
 #test(grid[a] == grid[b], false)
 
+// Content from different places in the source is still equal.
+#test([*hi*] == [*hi*], true)
+#test(eval("[*hi*]") == [*hi*], true)
+#test(eval("(((([*hi*]))))") == eval("[*hi*]"), true)
+#test([*hi*] == [_hi_], false)
+#test([*hi*] == [*ho*], false)
+
+// Functions in markup produce no content, other values are displayed.
+#let f(x) = x
+#test([#f] == [], true)
+#test([#calc.abs] == [], true)
+#test([#f(1)] == [1], true)
+#test([#1.5] == [1.5], true)
+
 ---
 // Test comparison operators.
 
@@ -226,3 +276,37 @@
 #let inc2 = inc.with(y: 2)
 #test(inc2(2), 4)
 #test(inc2(2, y: 4), 6)
+
+// Defaults can also come from a dictionary.
+#let inc3 = inc.with(..(y: 3))
+#test(inc3(1), 4)
+#test(inc3(1, y: 0), 1)
+
+// Pre-applied positional arguments come first and named arguments as well as
+// further positional arguments are passed through.
+#let describe(a, b, c, sep: "-") = (a, b, c).join(sep)
+#test(describe.with("x")("y", "z"), "x-y-z")
+#test(describe.with("x")("y", "z", sep: "+"), "x+y+z")
+#test(describe.with("x", sep: "/").with("y")("z"), "x/y/z")
+
+// Arguments are captured by value.
+#{
+  let n = 1
+  let add-n = add.with(n)
+  n = 10
+  test(add-n(1), 2)
+}
+
+---
+// Test `complement` method.
+#let nums = range(6)
+#test(nums.filter(calc.even.complement()), (1, 3, 5))
+#test(nums.filter(calc.even.complement().complement()), (0, 2, 4))
+
+#let within(x, max: 3) = x < max
+#test(nums.filter(within.complement()), (3, 4, 5))
+#test(nums.filter(within.with(max: 5).complement()), (5,))
+
+---
+// Error: 3-4 expected boolean, found integer
+#(x => x).complement()(1)
@@ -36,6 +36,17 @@
 #let rec(n) = rec(n) + 1
 #rec(1)
 
+---
+// Test that recursion through content hits the limit, too.
+// Error: 17-27 maximum function call depth exceeded
+#let rec(n) = [#rec(n + 1)]
+#rec(1)
+
+---
+// Recursion just below the limit is fine.
+#let count(n) = if n == 0 { 0 } else { count(n - 1) + 1 }
+#test(count(60), 60)
+
 ---
 #let f(x) = "hello"
 #let f(x) = if x != none { f(none) } else { "world" }
@@ -40,6 +40,10 @@
 #test("abc🏡def".slice(2, 7), "c🏡")
 #test("abc🏡def".slice(2, -2), "c🏡d")
 #test("abc🏡def".slice(-3, -1), "de")
+#test("abc🏡def".slice(-2), "ef")
+#test("abc".slice(1, 1), "")
+#test("äöü".slice(2, 4), "ö")
+#test("äöü".slice(-2), "ü")
 
 ---
 // Error: 2-21 string index -1 is not a character boundary
@@ -52,6 +56,57 @@
 #test("🏳️‍🌈!".clusters(), ("🏳️‍🌈", "!"))
 #test("🏳️‍🌈!".codepoints(), ("🏳", "\u{fe0f}", "\u{200d}", "🌈", "!"))
 
+---
+// Test the `count-graphemes` method.
+#test("".count-graphemes(), 0)
+#test("abc".count-graphemes(), 3)
+#test("🏳️‍🌈".count-graphemes(), 1)
+#test("🏳️‍🌈".codepoints().len(), 4)
+#test("🇩🇪!".count-graphemes(), 2)
+#test("🇩🇪!".codepoints().len(), 3)
+
+---
+// Test the `rev` method.
+#test("".rev(), "")
+#test("abc".rev(), "cba")
+#test("äöü".rev(), "üöä")
+#test("a🏳️‍🌈b".rev(), "b🏳️‍🌈a")
+#test("🇩🇪🇫🇷".rev(), "🇫🇷🇩🇪")
+#test("abc".rev().rev(), "abc")
+
+---
+// Test the `expand-tabs` method.
+#test("ab\tc".expand-tabs(), "ab  c")
+#test("\tx".expand-tabs(), "    x")
+#test("abcd\tx".expand-tabs(), "abcd    x")
+#test("a b\tc".expand-tabs(width: 8), "a b     c")
+#test("a\tb\tc".expand-tabs(width: 2), "a b c")
+#test("ab\ncd\tx".expand-tabs(), "ab\ncd  x")
+#test("ä\tx".expand-tabs(width: 3), "ä  x")
+#test("".expand-tabs(), "")
+
+---
+// Error: 28-29 number must be positive
+#"a\tb".expand-tabs(width: 0)
+
+---
+// Test the character class predicates.
+#test("0123".is-numeric(), true)
+#test("٣٤٥".is-numeric(), true)
+#test("½Ⅻ".is-numeric(), true)
+#test("12a".is-numeric(), false)
+#test("-1".is-numeric(), false)
+#test("".is-numeric(), false)
+#test("Straße".is-alpha(), true)
+#test("日本語".is-alpha(), true)
+#test("abc1".is-alpha(), false)
+#test("a b".is-alpha(), false)
+#test("".is-alpha(), false)
+#test("Typst2023".is-alphanumeric(), true)
+#test("x٣".is-alphanumeric(), true)
+#test("a_b".is-alphanumeric(), false)
+#test("".is-alphanumeric(), false)
+
 ---
 // Test the `contains` method.
 #test("abc".contains("b"), true)
@@ -62,6 +117,12 @@
 #test("1234g" in "1234f", false)
 #test("abc".contains(regex("^[abc]$")), false)
 #test("abc".contains(regex("^[abc]+$")), true)
+#test("abc".contains(""), true)
+#test("".contains(""), true)
+
+---
+// Error: 17-18 expected string or regular expression, found integer
+#"abc".contains(1)
 
 ---
 // Test the `starts-with` and `ends-with` methods.
@@ -72,6 +133,12 @@
 #test("Typst".ends-with(regex("\d*")), true)
 #test("Typst".ends-with(regex("\d+")), false)
 #test("Typ12".ends-with(regex("\d+")), true)
+#test("Typst".starts-with(""), true)
+#test("Typst".ends-with(""), true)
+
+---
+// Error: 20-24 expected string or regular expression, found none
+#"abc".starts-with(none)
 
 ---
 // Test the `find` and `position` methods.
@@ -126,6 +193,19 @@
 #test("123".replace(regex("\d$"), "_"), "12_")
 #test("123".replace(regex("\d{1,2}$"), "__"), "1__")
 
+---
+// Test the `replace-map` method.
+#test("abc".replace-map((:)), "abc")
+#test("a-b".replace-map(("-": "+", a: "b", b: "a")), "b+a")
+#test("schön".replace-map((s: "S", sch: "SH", "ö": "oe")), "SHoen")
+#test("sss".replace-map((s: "ss")), "ssssss")
+#test("aaa".replace-map((a: "x", aa: "y")), "yx")
+#test("abc".replace-map(("": "x", b: "")), "ac")
+
+---
+// Error: 2-27 expected string, found integer
+#"abc".replace-map((a: 1))
+
 ---
 // Test the `trim` method.
 #let str = "Typst, LaTeX, Word, InDesign"
@@ -148,6 +228,8 @@
 #test("123abc456".trim(regex("\d+"), at: end, repeat: false), "123abc")
 #test("123abc456".trim(regex("\d{1,2}$"), repeat: false), "123abc4")
 #test("hello world".trim(regex(".")), "")
+#test("\u{a0}\u{2003} abc\u{3000}\n".trim(), "abc")
+#test("--abc--".trim("--"), "abc")
 
 ---
 // Error: 17-21 expected either `start` or `end`
@@ -160,6 +242,15 @@
 #test("a123c".split(regex("\d")), ("a", "", "", "c"))
 #test("a123c".split(regex("\d+")), ("a", "c"))
 
+---
+// Test the `mask` method.
+#test("secret".mask(), "******")
+#test("jane@example.com".mask(keep-start: 2, keep-end: 2), "ja************om")
+#test("0123456789".mask(keep-end: 4, mask-char: "x"), "xxxxxx6789")
+#test("äöüß".mask(keep-start: 1, keep-end: 1), "ä**ß")
+#test("abc".mask(keep-start: 2, keep-end: 2), "abc")
+#test("".mask(), "")
+
 ---
 // Error: 2:1 expected quote
 #"hello\"
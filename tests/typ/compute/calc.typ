@@ -15,6 +15,29 @@
 ---
 #test(calc.round(calc.e, digits: 2), 2.72)
 #test(calc.round(calc.pi, digits: 2), 3.14)
+#test(calc.round(3.14159, digits: 2), 3.14)
+#test(calc.round(1234, digits: -2), 1200.0)
+#test(calc.round(1250, digits: -2), 1300.0)
+#test(calc.round(12, digits: 1), 12.0)
+
+---
+// Test rounding functions.
+#test(calc.floor(3.7), 3)
+#test(calc.floor(-3.2), -4)
+#test(calc.ceil(3.2), 4)
+#test(calc.ceil(-3.7), -3)
+#test(calc.round(2.5), 3)
+#test(type(calc.floor(3.7)), "integer")
+#test(calc.floor(2.7pt), 2pt)
+#test(calc.ceil(2.2em), 3em)
+#test(calc.round(1.6pt + 0.4em), 2pt + 0em)
+#test(calc.round(4.567pt, digits: 1), 4.6pt)
+#test(calc.ceil(44.5deg), 45deg)
+#test(calc.round(30.4deg), 30deg)
+
+---
+// Error: 13-17 expected integer, float, length, or angle, found string
+#calc.floor("hi")
 
 ---
 // Error: 6-10 expected boolean, integer, float, or string, found length
@@ -28,10 +51,52 @@
 // Error: 6-12 not a valid integer
 #int("nope")
 
+---
+// Test parsing integers in other bases.
+#test(int("ff", base: 16), 255)
+#test(int("FF", base: 16), 255)
+#test(int("-101", base: 2), -5)
+#test(int("777", base: 8), 511)
+#test(int("z", base: 36), 35)
+#test(int("42", base: 10), 42)
+
+---
+// Error: 6-9 not a valid integer
+#int("2", base: 2)
+
+---
+// Error: 18-20 base must be between 2 and 36
+#int("ff", base: 37)
+
+---
+// Error: 16-18 base is only supported for strings
+#int(10, base: 16)
+
 ---
 // Error: 8-15 not a valid float
 #float("1.2.3")
 
+---
+// Test trigonometric functions.
+#assert(calc.abs(calc.sin(90deg) - 1.0) < 1e-10)
+#assert(calc.abs(calc.cos(180deg) + 1.0) < 1e-10)
+#assert(calc.abs(calc.tan(45deg) - 1.0) < 1e-10)
+#assert(calc.abs(calc.sin(calc.pi / 2) - 1.0) < 1e-10)
+#test(calc.sin(0), 0.0)
+#test(type(calc.cos(0deg)), "float")
+#test(calc.asin(1), 90deg)
+#test(calc.acos(1), 0deg)
+#test(calc.atan(0.0), 0deg)
+#test(type(calc.atan(1)), "angle")
+
+---
+// Error: 12-15 arcsin must be between -1 and 1
+#calc.asin(1.5)
+
+---
+// Error: 12-14 arccos must be between -1 and 1
+#calc.acos(-2)
+
 ---
 // Test the `abs` function.
 #test(calc.abs(-3), 3)
@@ -41,6 +106,8 @@
 #test(calc.abs(-3.14), 3.14)
 #test(calc.abs(50%), 50%)
 #test(calc.abs(-25%), 25%)
+#test(calc.abs(-3pt), 3pt)
+#test(calc.abs(-90deg), 90deg)
 
 ---
 // Error: 11-22 expected integer, float, length, angle, ratio, or fraction, found string
@@ -60,6 +127,29 @@
 #test(calc.mod(5, -3), 2)
 #test(calc.mod(22.5, 10), 2.5)
 #test(calc.mod(9, 4.5), 0)
+#test(calc.mod(7, 3), 1)
+#test(calc.mod(-7, 3), -1)
+#test(calc.mod(-7.5, 2), -1.5)
+#test(calc.mod(-9223372036854775807 - 1, -1), 0)
+
+---
+// Test the `quo` function.
+#test(calc.quo(7, 3), 2)
+#test(calc.quo(-7, 3), -3)
+#test(calc.quo(7, -3), -3)
+#test(calc.quo(-7, -3), 2)
+#test(calc.quo(6, -3), -2)
+#test(calc.quo(7.5, 2), 3.0)
+#test(calc.quo(-7.5, 2), -4.0)
+#test(calc.quo(-9223372036854775807 - 1, -1), 9223372036854775808.0)
+
+---
+// The quotient rounds down, while the modulus truncates.
+#test(calc.quo(-7, 2), -4)
+#test(calc.mod(-7, 2), -1)
+#test(calc.quo(-7, 2) * 2 + calc.mod(-7, 2), -9)
+#test(-7 - calc.quo(-7, 2) * 2, 1)
+#test(calc.quo(7, 2) * 2 + calc.mod(7, 2), 7)
 
 ---
 // Error: 14-15 divisor must not be zero
@@ -69,12 +159,67 @@
 // Error: 16-19 divisor must not be zero
 #calc.mod(3.0, 0.0)
 
+---
+// Error: 14-15 divisor must not be zero
+#calc.quo(5, 0)
+
+---
+// Test roots, exponentials, and logarithms.
+#test(calc.sqrt(9), 3.0)
+#test(calc.sqrt(2.25), 1.5)
+#test(calc.exp(0), 1.0)
+#test(calc.exp(1), calc.e)
+#test(calc.ln(1), 0.0)
+#test(calc.ln(calc.e), 1.0)
+#test(calc.log(1000), 3.0)
+#test(calc.log(0.01), -2.0)
+#test(calc.log(8, base: 2), 3.0)
+#test(calc.log(81, base: 3), 4.0)
+
+---
+// Error: 12-14 cannot take square root of negative number
+#calc.sqrt(-1)
+
+---
+// Error: 10-11 value must be strictly positive
+#calc.ln(0)
+
+---
+// Error: 11-13 value must be strictly positive
+#calc.log(-1)
+
+---
+// Error: 21-22 base must be strictly positive and not one
+#calc.log(10, base: 1)
+
+---
+// Test the `pow` function.
+#test(calc.pow(2, 10), 1024)
+#test(type(calc.pow(2, 10)), "integer")
+#test(calc.pow(2, 0), 1)
+#test(calc.pow(-3, 3), -27)
+#test(calc.pow(2, -1), 0.5)
+#test(calc.pow(4, 0.5), 2.0)
+#test(calc.pow(2.5, 2), 6.25)
+#test(calc.pow(0, -1), calc.inf)
+#test(calc.pow(2, 64), 18446744073709551616.0)
+#test(calc.pow(2, 5000000000), calc.inf)
+
+---
+// Error: 15-18 the result is not a real number
+#calc.pow(-8, 0.5)
+
 ---
 // Test the `min` and `max` functions.
 #test(calc.min(2, -4), -4)
 #test(calc.min(3.5, 1e2, -0.1, 3), -0.1)
 #test(calc.max(-3, 11), 11)
 #test(calc.min("hi"), "hi")
+#test(calc.max(2, 2.5, -1), 2.5)
+#test(calc.max(1, 1.0), 1)
+#test(calc.min(7pt), 7pt)
+#test(calc.min(2pt, 1cm, -3pt), -3pt)
+#test(calc.max(2pt, 1cm, -3pt), 1cm)
 
 ---
 // Error: 10-12 expected at least one value
@@ -84,6 +229,62 @@
 // Error: 14-18 cannot compare integer and string
 #calc.min(1, "hi")
 
+---
+// Error: 16-21 cannot compare length and angle
+#calc.max(1pt, 90deg)
+
+---
+// Test the `clamp` function.
+#test(calc.clamp(5, 0, 10), 5)
+#test(calc.clamp(-5, 0, 10), 0)
+#test(calc.clamp(2.5, 0, 10), 2.5)
+#test(calc.clamp(15, 0, 1.5), 1.5)
+#test(calc.clamp(-2pt, 0pt, 1cm), 0pt)
+#test(calc.clamp(3cm, 0pt, 1cm), 1cm)
+#test(calc.clamp(1em, 0em, 2em), 1em)
+#test(calc.clamp(400deg, 0deg, 360deg), 360deg)
+#test(calc.clamp(150%, 0%, 100%), 100%)
+#test(calc.clamp(1fr, 2fr, 3fr), 2fr)
+
+---
+// Error: 20-21 max must be greater than or equal to min
+#calc.clamp(5, 10, 0)
+
+---
+// Error: 24-27 max must be greater than or equal to min
+#calc.clamp(5pt, 10pt, 0pt)
+
+---
+// Error: 12-24 cannot compare length and integer
+#calc.clamp(5pt, 0, 10)
+
+---
+// Error: 12-28 cannot compare length and length
+#calc.clamp(1em, 0pt, 10pt)
+
+---
+// Error: 13-16 expected integer, float, length, angle, ratio, or fraction, found string
+#calc.clamp("a", "b", "c")
+
+---
+// Test the `lerp` function.
+#test(calc.lerp(0pt, 10pt, 25%), 2.5pt)
+#test(calc.lerp(0deg, 90deg, 0.5), 45deg)
+#test(calc.lerp(0%, 50%, 50%), 25%)
+#test(calc.lerp(1fr, 3fr, 0.5), 2fr)
+#test(calc.lerp(1, 2, 0.5), 1.5)
+#test(type(calc.lerp(0, 2, 0.5)), "float")
+#test(calc.lerp(1, 2, 150%), 2.5)
+#test(calc.lerp(1, 2, -1), 0.0)
+
+---
+// Error: 11-28 cannot interpolate between length and angle
+#calc.lerp(1pt, 90deg, 0.5)
+
+---
+// Error: 22-25 expected float or ratio, found string
+#calc.lerp(0pt, 1pt, "a")
+
 ---
 // Test the `range` function.
 #test(range(4), (0, 1, 2, 3))
@@ -104,6 +305,14 @@
 // Error: 11-14 expected integer, found float
 #range(1, 2.0)
 
+---
+// Integral floats must be converted explicitly.
+#test(range(int(6 / 2)), (0, 1, 2))
+
+---
+// Error: 8-13 expected integer, found float
+#range(6 / 2)
+
 ---
 // Error: 17-22 expected integer, found string
 #range(4, step: "one")
@@ -13,6 +13,80 @@
 #test(rgb(40, 30, 20).darken(10%), rgb(36, 27, 18))
 #test(rgb("#133337").negate(), rgb(236, 204, 200))
 #test(white.lighten(100%), white)
+#test(rgb("#808080").lighten(100%), white)
+#test(rgb("#808080").darken(100%), black)
+#test(rgb(10, 20, 30, 40).lighten(100%), rgb(255, 255, 255, 40))
+#test(rgb(10, 20, 30, 40).darken(50%), rgb(5, 10, 15, 40))
+#test(rgb(200, 100, 0).lighten(150%), white)
+#test(rgb(200, 100, 0).darken(150%), black)
+#test(luma(100).lighten(50%), luma(178))
+
+// Test HSL colors.
+#test(hsl(0deg, 100%, 50%), rgb(255, 0, 0))
+#test(hsl(120deg, 100%, 25%), rgb(0, 128, 0))
+#test(hsl(240deg, 100%, 50%), rgb(0, 0, 255))
+#test(hsl(-120deg, 100%, 50%), hsl(240deg, 100%, 50%))
+#test(hsl(200deg, 0%, 50%), rgb(128, 128, 128))
+#test(hsl(0deg, 100%, 100%), white)
+#test(hsl(0deg, 100%, 0%), black)
+#test(rgb(0, 0, 255).hue(), 240deg)
+#test(rgb(0, 0, 255).saturation(), 100%)
+#test(rgb(0, 0, 255).lightness(), 50%)
+#test(luma(128).hue(), 0deg)
+#test(luma(128).saturation(), 0%)
+#test(white.lightness(), 100%)
+#{
+  let color = rgb("#239dad")
+  test(hsl(color.hue(), color.saturation(), color.lightness()), color)
+}
+
+// Test alpha manipulation.
+#test(rgb(255, 0, 0).with-alpha(50%), rgb(255, 0, 0, 128))
+#test(rgb(255, 0, 0, 10).with-alpha(100%), rgb(255, 0, 0))
+#test(luma(0).with-alpha(0%), rgb(0, 0, 0, 0))
+#test(rgb(0, 0, 255, 128).fade(50%), rgb(0, 0, 255, 64))
+#test(rgb(0, 0, 255, 128).fade(200%), rgb(0, 0, 255, 128))
+#test(rgb(0, 0, 255).with-alpha(150%), rgb(0, 0, 255))
+
+// Test alpha premultiplication.
+#test(rgb(255, 128, 0, 128).premultiply(), rgb(128, 64, 0, 128))
+#test(rgb(128, 64, 0, 128).unpremultiply(), rgb(255, 128, 0, 128))
+#test(rgb(10, 20, 30).premultiply(), rgb(10, 20, 30))
+#test(rgb(10, 20, 30, 0).premultiply(), rgb(0, 0, 0, 0))
+#test(rgb(10, 20, 30, 0).unpremultiply(), rgb(0, 0, 0, 0))
+
+// Test color string formatting.
+#test(rgb(255, 0, 0).to-hex(), "#ff0000")
+#test(rgb(255, 0, 0).to-rgb-string(), "rgb(255, 0, 0)")
+#test(rgb(0, 128, 255, 128).to-hex(), "#0080ff80")
+#test(rgb(0, 128, 255, 128).to-rgb-string(), "rgba(0, 128, 255, 0.502)")
+#test(rgb(10, 20, 30, 0).to-rgb-string(), "rgba(10, 20, 30, 0)")
+#test(luma(255).to-hex(), "#ffffff")
+
+// Test color mixing.
+#test(mix(black, white, 0.5), rgb(128, 128, 128))
+#test(mix(black, white, 50%), rgb("#808080"))
+#test(mix(red, blue, 0), red)
+#test(mix(red, blue, 1.0), blue)
+#test(mix(red, blue, 150%), blue)
+#test(mix(red, blue, -1), red)
+#test(mix(rgb(0, 0, 0, 0), rgb(0, 0, 0, 200), 25%), rgb(0, 0, 0, 50))
+#test(mix(cmyk(0%, 0%, 0%, 100%), luma(255), 50%), rgb(128, 128, 128))
+
+---
+// Test color names in rgb.
+#test(rgb("red"), red)
+#test(rgb("navy"), navy)
+#test(rgb("#ff4136"), red)
+
+---
+// Strings are not colors by themselves.
+// Error: 6-11 expected color, found string
+#mix("red", blue, 50%)
+
+---
+// Error: 6-15 color string contains non-hexadecimal letters
+#rgb("blurple")
 
 ---
 // Test gray color conversion.
@@ -40,6 +114,14 @@
 // Error: 21-26 expected integer or ratio, found boolean
 #rgb(10%, 20%, 30%, false)
 
+---
+// Error: 2-20 type ratio has no method `lighten`
+#(50%).lighten(10%)
+
+---
+// Error: 13-18 expected color, found integer
+#mix(black, 12345, 50%)
+
 ---
 // Ref: true
 #let envelope = symbol(
@@ -62,10 +144,99 @@
 #test(str(123), "123")
 #test(str(50.14), "50.14")
 #test(str(10 / 3).len() > 10, true)
+#test(str(true), "true")
+#test(str(false), "false")
+#test(str("hi"), "hi")
+#test(str(<intro>), "intro")
+#test(str(2.5cm), repr(2.5cm))
+#test(str(1pt + 2em), repr(1pt + 2em))
+#test(str(90deg), "90deg")
+#test(str(50%), "50%")
+#test(str(50% + 1pt), repr(50% + 1pt))
+#test(str(2fr), "2fr")
+#test(str(red), repr(red))
+
+---
+// Test formatting integers in other bases.
+#test(str(255, base: 16), "ff")
+#test(str(5, base: 2), "101")
+#test(str(-255, base: 16), "-ff")
+#test(str(0, base: 8), "0")
+#test(str(35, base: 36), "z")
+#test(int(str(-9223372036854775807 - 1, base: 2), base: 2), -9223372036854775807 - 1)
 
 ---
-// Error: 6-8 expected integer, float, label, or string, found content
+// Error: 16-17 base must be between 2 and 36
+#str(10, base: 1)
+
+---
+// Error: 18-20 base is only supported for integers
+#str("ff", base: 16)
+
+---
+// Error: 6-8 expected integer, float, boolean, length, angle, ratio, relative length, fraction, color, label, or string, found content
 #str([])
 
+---
+// Error: 6-10 expected integer, float, boolean, length, angle, ratio, relative length, fraction, color, label, or string, found array
+#str((1,))
+
+---
+// Error: 6-9 expected integer, float, boolean, length, angle, ratio, relative length, fraction, color, label, or string, found dictionary
+#str((:))
+
+---
+// Test regex construction.
+#test(regex("\d+"), regex("\d+"))
+#test(regex("\d+") == regex("\d*"), false)
+#test(repr(regex("\d+")), "regex(\"\\\\d+\")")
+#test(type(regex("a")), "regular expression")
+#test("a1b22".matches(regex("\d+")).map(m => m.text), ("1", "22"))
+
+---
+// Test conversion to array.
+#test(array(none), ())
+#test(array(1), (1,))
+#test(array("a"), ("a",))
+#test(array((a: 1)), ((a: 1),))
+#test(array(()), ())
+#test(array((1, 2)), (1, 2))
+
 ---
 #assert(range(2, 5) == (2, 3, 4))
+
+---
+// Test linspace and arange.
+#test(linspace(0, 1, 5), (0.0, 0.25, 0.5, 0.75, 1.0))
+#test(linspace(10, 0, 3), (10.0, 5.0, 0.0))
+#test(linspace(0, 1, 4).last(), 1.0)
+#test(linspace(2, 4, 1), (2.0,))
+#test(arange(0, 1, 0.25), (0.0, 0.25, 0.5, 0.75))
+#test(arange(0, 1, 0.3), (0.0, 0.3, 0.6, 0.8999999999999999))
+#test(arange(1, 0, -0.5), (1.0, 0.5))
+#test(arange(0, 1, -1), ())
+#test(arange(2, 2, 1), ())
+
+---
+// Error: 17-18 number must be positive
+#linspace(0, 1, 0)
+
+---
+// Error: 15-18 step must not be zero
+#arange(0, 1, 0.0)
+
+---
+// Error: 17-29 cannot generate more than 1000000 numbers
+#linspace(0, 1, 100000000000)
+
+---
+// Error: 12-20 number must be finite
+#arange(0, calc.inf, 1)
+
+---
+// Error: 15-23 number must be finite
+#arange(0, 1, calc.nan)
+
+---
+// Error: 8-20 cannot generate more than 1000000 numbers
+#arange(0, 1e10, 1)
@@ -41,6 +41,47 @@
 // Error: 7-18 failed to parse json file: syntax error in line 3
 #json("/bad.json")
 
+---
+// Test parsing JSON from a string.
+#let data = json(data: "{\"name\": \"Debby\", \"weight\": 150, \"height\": 1.5}")
+#test(data, (name: "Debby", weight: 150, height: 1.5))
+#test(type(data.weight), "integer")
+#test(type(data.height), "float")
+#test(json(data: "[1, null, true]"), (1, none, true))
+
+---
+// Error: 13-26 failed to parse json data: syntax error in line 1
+#json(data: "{\"a\": 1,}")
+
+---
+// Test pretty-printing JSON.
+#test(pretty-json((1, "a\"b", none, true)), "[\n  1,\n  \"a\\\"b\",\n  null,\n  true\n]")
+#test(pretty-json((b: (x: 1.5), a: 12pt)), "{\n  \"a\": \"12pt\",\n  \"b\": {\n    \"x\": 1.5\n  }\n}")
+#test(pretty-json(()), "[]")
+#test(repr(pretty-json), "pretty-json")
+#test(pretty-json(calc.inf), "null")
+#test(json(data: pretty-json((a: (1, 2), b: "c"))), (a: (1, 2), b: "c"))
+
+---
+// Test reading JSON5 data.
+#test(json5("/zoo.json"), json("/zoo.json"))
+#let data = json5(data: "{
+  // A comment.
+  name: 'Debby',
+  weight: 150,
+  tags: ['rhino', 'big',],
+}")
+#test(data, (name: "Debby", weight: 150, tags: ("rhino", "big")))
+#test(json5(data: "[1, null, true, +2.5, 0x10,]"), (1, none, true, 2.5, 16))
+
+---
+// Error: 8-19 failed to parse json5 file: syntax error in line 3
+#json5("/bad.json")
+
+---
+// Error: 14-28 failed to parse json5 data: syntax error in line 2
+#json5(data: "{\n  a: 1 2}")
+
 ---
 // Test reading XML data.
 #let data = xml("/data.xml")
@@ -10,6 +10,130 @@
 #test(repr(ltr), "ltr")
 #test(repr((1, 2, false, )), "(1, 2, false)")
 
+---
+// Test diff.
+#test(diff(1, 1), "  1")
+#test(diff(1, 2), "- 1\n+ 2")
+#test(diff((1, 2), (1, 3)), "- (1, 2)\n+ (1, 3)")
+
+#let old = (name: "Typst", kind: "markup-based typesetter", year: 2023)
+#let new = (name: "Typst", kind: "markup-based typesetting system", year: 2023)
+#let lines = diff(old, new).split("\n")
+#test(lines.filter(line => line.starts-with("-")), ("-   kind: \"markup-based typesetter\",",))
+#test(lines.filter(line => line.starts-with("+")), ("+   kind: \"markup-based typesetting system\",",))
+#test(lines.filter(line => line.contains("name")), ("    name: \"Typst\",",))
+
+#let old = range(100)
+#let new = old.filter(n => n != 20) + (100,)
+#let lines = diff(old, new).split("\n")
+#test(lines.filter(line => not line.starts-with(" ")), ("-   20,", "+   100,"))
+#test(lines.len(), 103)
+
+---
+// Test empty.
+#test(empty(()), true)
+#test(empty((1,)), false)
+#test(empty((:)), true)
+#test(empty((a: none)), false)
+#test(empty(""), true)
+#test(empty(" "), false)
+#test(empty([]), true)
+#test(empty([ ]), false)
+#test(empty([#""]), false)
+#test(if empty(()) { "yes" } else { "no" }, "yes")
+
+---
+// Error: 8-9 expected array, dictionary, string, or content, found integer
+#empty(0)
+
+---
+// Test identity and const.
+#test(identity(5), 5)
+#test(identity(none), none)
+#test((1, 2, 3).map(identity), (1, 2, 3))
+#test(const(3)("anything"), 3)
+#test(const(3)(), 3)
+#test(const("a")(1, 2, key: 3), "a")
+#test((1, 2, 3).map(const(0)), (0, 0, 0))
+#test((1, 2, 3).fold(none, const(true)), true)
+#test(repr(const(3)), "const")
+
+---
+// Test cast.
+#test(cast(5, "integer"), 5)
+#test(type(cast(5, "float")), "float")
+#test(cast(5, "length", "float"), 5.0)
+#test(cast(50%, "length", "ratio", "auto"), 50%)
+#test(cast(auto, "length", "ratio", "auto"), auto)
+#test(type(cast(1pt, "relative length")), "relative length")
+#test(type(cast("hi", "content")), "content")
+#test(cast((1, 2), "dictionary", "array"), (1, 2))
+#test(cast(ltr, "direction"), ltr)
+
+---
+// Error: 6-38 expected length, ratio, or auto, found string
+#cast("a", "length", "ratio", "auto")
+
+---
+// Test curry.
+#let add(x, y) = x + y
+#test(curry(add)(1)(2), 3)
+#test(curry(add, 2)(1)(2), 3)
+#{
+  let join3(a, b, c) = a + b + c
+  let step = curry(join3)("a")
+  test(step("b")("c"), "abc")
+  test(step("x")("y"), "axy")
+}
+#test((1, 2, 3).map(curry(add)(10)), (11, 12, 13))
+#test(curry(calc.max, 3)(1)(5)(2), 5)
+#test(curry(x => x * 2)(4), 8)
+#test(repr(curry(calc.max, 3)(1)), "curry")
+
+---
+// Error: 7-17 cannot determine the number of arguments, please specify it
+#curry(calc.max)
+
+---
+// Error: 16-17 number must be positive
+#curry(x => x, 0)
+
+---
+// Test compose.
+#let double(x) = 2 * x
+#let increment(x) = x + 1
+#test(compose(double, increment)(3), 8)
+#test(compose(increment, double)(3), 7)
+#test(compose(str, calc.abs)(-5), "5")
+#test((1, 2, 3).map(compose(double, double)), (4, 8, 12))
+#test(compose(upper, compose(str, increment))(1), "2")
+#test(repr(compose(upper, str)), "compose")
+
+---
+// Error: 17-18 expected function, found integer
+#compose(upper, 1)
+
+---
+// Error: 25-26 unexpected argument
+#compose(upper, str)(1, 2)
+
+---
+// Test default.
+#test(default(none, 5), 5)
+#test(default(none, none), none)
+#test(default(0, 5), 0)
+#test(default(false, true), false)
+#test(default("", "fallback"), "")
+#test(default((), (1,)), ())
+
+---
+// Error: 9-15 missing argument: fallback
+#default(none)
+
+---
+// Error: 10-12 missing argument: value
+#identity()
+
 ---
 // Test panic.
 // Error: 7-9 panicked
@@ -25,6 +149,18 @@
 // Error: 7-24 panicked with: "this is wrong"
 #panic("this is wrong")
 
+---
+// Test panic in a branch that should be unreachable.
+// Error: 41-56 panicked with: "negative", -1
+#let f(x) = if x >= 0 { x } else { panic("negative", x) }
+#test(f(1), 1)
+#f(-1)
+
+---
+// Test passing assertions.
+#test(assert(1 < 2), none)
+#test(assert(true, message: "never shown"), none)
+
 ---
 // Test failing assertions.
 // Error: 8-16 assertion failed
@@ -45,6 +181,29 @@
 #test(type(1), "integer")
 #test(type(ltr), "direction")
 #test(type(10 / 3), "float")
+#test(type((1, "a")), "array")
+#test(type((a: 1)), "dictionary")
+#test(type(none), "none")
+#test(type(top + left), "2d alignment")
+#test(type(regex("a")), "regular expression")
+
+---
+// Test evaluating single expressions.
+#test(eval("1 + 2"), 3)
+#test(eval("(1, 2)"), (1, 2))
+#test(eval("(a: 1, b: \"x\")"), (a: 1, b: "x"))
+#test(eval("none"), none)
+#test(eval(""), none)
+
+---
+// Test that the representation of values evaluates back to them.
+#for v in (none, true, 1, -2.5, 10pt, 90deg, 50%, 1fr, "a\"b", (1, "2"), (a: (b: 3pt))) {
+  test(eval(repr(v)), v)
+}
+
+---
+// Error: 7-18 cannot add integer and string
+#eval("1 + \"a\"")
 
 ---
 #eval("[_Hello" + " World!_]")
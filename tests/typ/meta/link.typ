@@ -44,3 +44,15 @@ My cool #box(move(dx: 0.7cm, dy: 0.7cm, rotate(10deg, scale(200%, mylink))))
 ---
 // Link to page one.
 #link((page: 1, x: 10pt, y: 20pt))[Back to the start]
+
+---
+// Error: 7-25 missing key: "y"
+#link((page: 1, x: 10pt))[A]
+
+---
+// Error: 7-33 expected length, found string
+#link((page: 1, x: 10pt, y: "a"))[A]
+
+---
+// Error: 7-39 unexpected key "z", valid keys are "page", "x", and "y"
+#link((page: 1, x: 10pt, y: 0pt, z: 1))[A]
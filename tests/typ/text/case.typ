@@ -1,4 +1,4 @@
-// Test the `upper` and `lower` functions.
+// Test the `upper`, `lower` and `title` functions.
 // Ref: false
 
 ---
@@ -6,7 +6,30 @@
 #test(lower(memes), "are memes great?")
 #test(upper(memes), "ARE MEMES GREAT?")
 #test(upper("Ελλάδα"), "ΕΛΛΆΔΑ")
+#test(upper("straße"), "STRASSE")
+#test(lower("ÄÖÜ İstanbul"), "äöü i̇stanbul")
+
+---
+#test(title("the quick  brown\tfox"), "The Quick  Brown\tFox")
+#test(title("éléphant rose"), "Éléphant Rose")
+#test(title("mIxEd case"), "MIxEd Case")
+#test(title(""), "")
+#test(title("ßuper"), "SSuper")
 
 ---
 // Error: 8-9 expected string or content, found integer
 #upper(1)
+
+---
+// Error: 8-12 expected string, found boolean
+#title(true)
+
+---
+// Title case doesn't work on content because words can be split across
+// styled pieces of text.
+// Error: 7-19 expected string, found content
+#title[don't stop]
+
+---
+// Error: 7-29 expected string, found content
+#title[hello #strong[wor]ld]
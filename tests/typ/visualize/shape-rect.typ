@@ -50,6 +50,14 @@
 // Error: 15-38 unexpected key "cake", valid keys are "top-left", "top-right", "bottom-right", "bottom-left", "left", "top", "right", "bottom", and "rest"
 #rect(radius: (left: 10pt, cake: 5pt))
 
+---
+// Error: 14-36 expected relative length, found string
+#rect(inset: (left: 10pt, top: "a"))
+
+---
+// Error: 14-35 unexpected key "up", valid keys are "left", "top", "right", "bottom", "x", "y", and "rest"
+#rect(inset: (left: 10pt, up: 5pt))
+
 ---
 // Error: 15-21 expected length, color, stroke, none, dictionary, or auto, found array
 #rect(stroke: (1, 2))